@@ -0,0 +1,180 @@
+//! Attribute and derive macros for
+//! [`tracing-unwrap`](https://docs.rs/tracing-unwrap).
+//!
+//! These are re-exported by `tracing-unwrap` with its **`attributes`** and
+//! **`derive`** features, and should be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, ItemFn, LitStr, Token};
+
+/// The arguments of `#[unwrap_context("...", arg, ...)]`.
+struct ContextArgs {
+    context: LitStr,
+    fields: Vec<Ident>,
+}
+
+impl Parse for ContextArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let context = input.parse()?;
+        let mut fields = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            fields.push(input.parse()?);
+        }
+        Ok(ContextArgs { context, fields })
+    }
+}
+
+/// Attaches a context string to every failure of `tracing-unwrap` that
+/// happens while the function runs, as the `unwrap.context` field.
+///
+/// The context may be followed by names of the function's arguments, whose
+/// `Debug` renderings are appended to it. They are rendered when the function
+/// is called, so choose cheap ones.
+///
+/// ```ignore
+/// #[unwrap_context("loading user profile", user_id)]
+/// fn load_profile(user_id: u64) -> Profile {
+///     // Logged with `unwrap.context="loading user profile (user_id=42)"`.
+///     fetch(user_id).unwrap_or_log()
+/// }
+/// ```
+///
+/// Contexts nest, with the outermost one first. On an `async fn`, the context
+/// applies whenever its future is polled.
+#[proc_macro_attribute]
+pub fn unwrap_context(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let ContextArgs { context, fields } = parse_macro_input!(attr as ContextArgs);
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+
+    let context = if fields.is_empty() {
+        quote!(::std::borrow::Cow::Borrowed(#context))
+    } else {
+        let rendered: Vec<_> = fields.iter().map(|f| format!("{}={{:?}}", f)).collect();
+        let format = format!(
+            "{} ({})",
+            context.value().replace('{', "{{").replace('}', "}}"),
+            rendered.join(", ")
+        );
+        let format = LitStr::new(&format, context.span());
+        quote!(::std::borrow::Cow::Owned(
+            ::std::format!(#format, #(#fields),*)
+        ))
+    };
+
+    let body = if sig.asyncness.is_some() {
+        quote!({
+            ::tracing_unwrap::__private::in_context(#context, async move #block).await
+        })
+    } else {
+        quote!({
+            let _context = ::tracing_unwrap::__private::enter_context(#context);
+            #block
+        })
+    };
+
+    quote!(#(#attrs)* #vis #sig #body).into()
+}
+
+/// Implements `LevelFor` from `#[level(...)]` attributes, so that the
+/// severity policy of an error type is declared next to its definition.
+///
+/// Each variant of an enum may carry a `#[level(error)]`, `#[level(warn)]`,
+/// `#[level(info)]`, `#[level(debug)]` or `#[level(trace)]` attribute. The
+/// others take the level of the attribute on the type, if any, or `ERROR`.
+///
+/// ```ignore
+/// #[derive(Debug, LevelFor)]
+/// #[level(warn)]
+/// enum FetchError {
+///     #[level(debug)]
+///     CacheMiss,
+///     Timeout(Duration),
+///     #[level(error)]
+///     Corrupt { offset: u64 },
+/// }
+/// ```
+#[proc_macro_derive(LevelFor, attributes(level))]
+pub fn derive_level_for(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match level_for(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn level_for(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let default = level_attr(&input.attrs)?.unwrap_or_else(|| quote!(ERROR));
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let name = &variant.ident;
+                    let level = level_attr(&variant.attrs)?.unwrap_or_else(|| default.clone());
+                    let pattern = match variant.fields {
+                        Fields::Named(_) => quote!(Self::#name { .. }),
+                        Fields::Unnamed(_) => quote!(Self::#name(..)),
+                        Fields::Unit => quote!(Self::#name),
+                    };
+                    Ok(quote!(#pattern => ::tracing_unwrap::__private::Level::#level,))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(match self { #(#arms)* })
+        }
+        Data::Struct(_) => quote!(::tracing_unwrap::__private::Level::#default),
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`LevelFor` can't be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tracing_unwrap::LevelFor for #name #ty_generics #where_clause {
+            #[allow(unreachable_code)]
+            fn level(&self) -> ::tracing_unwrap::__private::Level {
+                #body
+            }
+        }
+    })
+}
+
+/// Reads the level of a `#[level(...)]` attribute, if there is one, as the
+/// name of a `Level` constant.
+fn level_attr(attrs: &[Attribute]) -> syn::Result<Option<TokenStream2>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("level")) else {
+        return Ok(None);
+    };
+    let level: Ident = attr.parse_args()?;
+    let constant = match level.to_string().as_str() {
+        "error" => "ERROR",
+        "warn" => "WARN",
+        "info" => "INFO",
+        "debug" => "DEBUG",
+        "trace" => "TRACE",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                level,
+                "expected one of `error`, `warn`, `info`, `debug` or `trace`",
+            ))
+        }
+    };
+    let constant = Ident::new(constant, level.span());
+    Ok(Some(quote!(#constant)))
+}
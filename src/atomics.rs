@@ -0,0 +1,87 @@
+//! Extensions for atomic types.
+
+use crate::{callsite, failed_with};
+use core::sync::atomic::{self, Ordering};
+
+/// Extension trait for the atomic integer and boolean types.
+pub trait AtomicExt {
+    /// The type of the atomic's value.
+    type Value;
+
+    /// Updates the value with `f`, like `fetch_update`, yielding the previous
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` rejects the update by returning [`None`], logging the
+    /// observed value and the number of compare-and-swap attempts made to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn fetch_update_or_log<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Self::Value
+    where
+        F: FnMut(Self::Value) -> Option<Self::Value>;
+}
+
+macro_rules! impl_atomic_ext {
+    ($($(#[$cfg:meta])* $atomic:ident($value:ty)),+ $(,)?) => {$(
+        $(#[$cfg])*
+        impl AtomicExt for atomic::$atomic {
+            type Value = $value;
+
+            #[inline]
+            #[track_caller]
+            fn fetch_update_or_log<F>(
+                &self,
+                set_order: Ordering,
+                fetch_order: Ordering,
+                mut f: F,
+            ) -> $value
+            where
+                F: FnMut($value) -> Option<$value>,
+            {
+                callsite::register(concat!(stringify!($atomic), "::fetch_update_or_log"));
+                let mut attempts = 0_u32;
+                let update = |value| {
+                    attempts += 1;
+                    f(value)
+                };
+                match self.fetch_update(set_order, fetch_order, update) {
+                    Ok(previous) => previous,
+                    Err(observed) => failed_with(
+                        concat!(stringify!($atomic), "::fetch_update_or_log"),
+                        concat!(
+                            "called `", stringify!($atomic),
+                            "::fetch_update_or_log()` with an update that was rejected"
+                        ),
+                        &format_args!(
+                            "observed value {:?}, after {} attempts",
+                            observed, attempts
+                        ),
+                    ),
+                }
+            }
+        }
+    )+};
+}
+
+impl_atomic_ext!(
+    AtomicBool(bool),
+    AtomicI8(i8),
+    AtomicI16(i16),
+    AtomicI32(i32),
+    #[cfg(target_has_atomic = "64")]
+    AtomicI64(i64),
+    AtomicIsize(isize),
+    AtomicU8(u8),
+    AtomicU16(u16),
+    AtomicU32(u32),
+    #[cfg(target_has_atomic = "64")]
+    AtomicU64(u64),
+    AtomicUsize(usize),
+);
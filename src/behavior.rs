@@ -0,0 +1,47 @@
+//! What a fatal failure does once its event is emitted.
+
+use std::sync::{PoisonError, RwLock};
+
+static BEHAVIOR: RwLock<FailureBehavior> = RwLock::new(FailureBehavior::Panic);
+
+/// What a fatal failure does once its event is emitted and the shutdown hook,
+/// if any, has run.
+///
+/// ```
+/// use tracing_unwrap::FailureBehavior;
+///
+/// // Abort, so that the process leaves a core dump behind.
+/// tracing_unwrap::set_failure_behavior(FailureBehavior::Abort);
+/// # tracing_unwrap::set_failure_behavior(FailureBehavior::Panic);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FailureBehavior {
+    /// Panics, as by default. The panic message or payload is chosen by the
+    /// **`panic-quiet`** and **`panic-payload`** features and by a panic
+    /// formatter, if one is set.
+    #[default]
+    Panic,
+    /// Aborts the process, e.g. to get a core dump, without unwinding.
+    Abort,
+    /// Exits the process with the given code, without unwinding or running
+    /// destructors.
+    Exit(i32),
+}
+
+/// Sets what fatal failures do once their event is emitted, by default
+/// [`FailureBehavior::Panic`].
+///
+/// A failure during an unwind, or once a circuit breaker for fatal failures
+/// has tripped, aborts regardless.
+pub fn set_failure_behavior(behavior: FailureBehavior) {
+    *BEHAVIOR.write().unwrap_or_else(PoisonError::into_inner) = behavior;
+}
+
+/// Aborts or exits the process, unless fatal failures are to panic.
+pub(crate) fn apply() {
+    match *BEHAVIOR.read().unwrap_or_else(PoisonError::into_inner) {
+        FailureBehavior::Panic => {}
+        FailureBehavior::Abort => std::process::abort(),
+        FailureBehavior::Exit(code) => std::process::exit(code),
+    }
+}
@@ -0,0 +1,94 @@
+//! Extensions for the fixed-capacity collections of the [`heapless`] crate.
+
+use crate::{callsite, failed_with};
+use core::hash::{BuildHasher, Hash};
+
+/// Extension trait for [`heapless::Vec`].
+pub trait HeaplessVecExt<T> {
+    /// Appends an `item` to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is already full, logging its capacity and the
+    /// attempted length to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn push_or_log(&mut self, item: T);
+
+    /// Inserts an `element` at position `index` within the vector, shifting
+    /// all elements after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is already full, logging its capacity and the
+    /// attempted length to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn insert_or_log(&mut self, index: usize, element: T);
+}
+
+impl<T, const N: usize> HeaplessVecExt<T> for heapless::Vec<T, N> {
+    #[inline]
+    #[track_caller]
+    fn push_or_log(&mut self, item: T) {
+        callsite::register("heapless::Vec::push_or_log");
+        let attempted = self.len() + 1;
+        if self.push(item).is_err() {
+            failed_with(
+                "heapless::Vec::push_or_log",
+                "called `heapless::Vec::push_or_log()` on a full vector",
+                &format_args!("capacity {}, attempted length {}", N, attempted),
+            );
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn insert_or_log(&mut self, index: usize, element: T) {
+        callsite::register("heapless::Vec::insert_or_log");
+        let attempted = self.len() + 1;
+        if self.insert(index, element).is_err() {
+            failed_with(
+                "heapless::Vec::insert_or_log",
+                "called `heapless::Vec::insert_or_log()` on a full vector",
+                &format_args!("capacity {}, attempted length {}", N, attempted),
+            );
+        }
+    }
+}
+
+/// Extension trait for [`heapless::IndexMap`].
+pub trait HeaplessIndexMapExt<K, V> {
+    /// Inserts a key-value pair into the map, returning the previous value
+    /// for `key`, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not yet in the map and the map is already full,
+    /// logging its capacity and the attempted length to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn insert_or_log(&mut self, key: K, value: V) -> Option<V>;
+}
+
+impl<K, V, S, const N: usize> HeaplessIndexMapExt<K, V> for heapless::IndexMap<K, V, S, N>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    #[inline]
+    #[track_caller]
+    fn insert_or_log(&mut self, key: K, value: V) -> Option<V> {
+        callsite::register("heapless::IndexMap::insert_or_log");
+        let attempted = self.len() + 1;
+        match self.insert(key, value) {
+            Ok(previous) => previous,
+            Err(_) => failed_with(
+                "heapless::IndexMap::insert_or_log",
+                "called `heapless::IndexMap::insert_or_log()` on a full map",
+                &format_args!("capacity {}, attempted length {}", N, attempted),
+            ),
+        }
+    }
+}
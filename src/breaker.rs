@@ -0,0 +1,143 @@
+//! Changing what failures do once they happen too often.
+
+use crate::facade::Level;
+use crate::{clock, filter, reporter, FailureRecord};
+use std::collections::{HashMap, VecDeque};
+use std::panic::Location;
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+/// What a [`CircuitBreaker`] makes failures do once it trips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Trip {
+    /// Fatal failures abort the process instead of panicking.
+    Abort,
+    /// Discarded errors panic instead of only being logged.
+    Panic,
+}
+
+/// A policy that changes what failures do once too many of them happen within
+/// a time window, to stop a crash loop from flapping silently.
+///
+/// ```
+/// use std::time::Duration;
+/// use tracing_unwrap::CircuitBreaker;
+///
+/// // Panics are caught and the work restarted, unless 5 of them happen within
+/// // a minute: then the process aborts, for a supervisor to take over.
+/// let breaker = CircuitBreaker::abort_after(5, Duration::from_secs(60));
+/// tracing_unwrap::set_circuit_breaker(Some(breaker));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    trip: Trip,
+    threshold: usize,
+    window: Duration,
+    per_callsite: bool,
+}
+
+impl CircuitBreaker {
+    /// Makes fatal failures abort the process instead of panicking, once
+    /// `threshold` of them have happened within `window`. A `threshold` of 0
+    /// is taken as 1.
+    pub fn abort_after(threshold: usize, window: Duration) -> Self {
+        CircuitBreaker {
+            trip: Trip::Abort,
+            threshold: threshold.max(1),
+            window,
+            per_callsite: false,
+        }
+    }
+
+    /// Makes discarded errors panic instead of only being logged, once
+    /// `threshold` of them have happened within `window`. A `threshold` of 0
+    /// is taken as 1.
+    pub fn panic_after(threshold: usize, window: Duration) -> Self {
+        CircuitBreaker {
+            trip: Trip::Panic,
+            threshold: threshold.max(1),
+            window,
+            per_callsite: false,
+        }
+    }
+
+    /// Counts the failures of each callsite separately, rather than all
+    /// failures together.
+    pub fn per_callsite(self) -> Self {
+        CircuitBreaker {
+            per_callsite: true,
+            ..self
+        }
+    }
+}
+
+static BREAKER: RwLock<Option<CircuitBreaker>> = RwLock::new(None);
+
+/// The times of the recent failures, by callsite, or under `None` for a
+/// breaker that counts all failures together.
+static HISTORY: Mutex<Option<HashMap<Option<&'static Location<'static>>, VecDeque<Duration>>>> =
+    Mutex::new(None);
+
+/// Installs a process-wide [`CircuitBreaker`], replacing any previous one and
+/// forgetting the failures counted so far. Pass `None` to remove it.
+pub fn set_circuit_breaker(breaker: Option<CircuitBreaker>) {
+    let mut current = BREAKER.write().unwrap_or_else(PoisonError::into_inner);
+    *HISTORY.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    *current = breaker;
+}
+
+/// Counts `record` against the installed breaker, if it applies to failures
+/// like it, and returns whether the breaker has tripped.
+///
+/// The failure that trips the breaker comes with an event of its own,
+/// which is filtered and reported like any failure.
+pub(crate) fn trips(record: &FailureRecord<'_>) -> bool {
+    let (count, window) = {
+        let breaker = BREAKER.read().unwrap_or_else(PoisonError::into_inner);
+        let Some(breaker) = &*breaker else {
+            return false;
+        };
+        let applies = match breaker.trip {
+            Trip::Abort => record.is_fatal(),
+            Trip::Panic => !record.is_fatal(),
+        };
+        if !applies {
+            return false;
+        }
+
+        let key = breaker.per_callsite.then(|| record.location());
+        let now = clock::now();
+        let mut history = HISTORY.lock().unwrap_or_else(PoisonError::into_inner);
+        let times = history
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_default();
+        while times
+            .front()
+            .is_some_and(|&time| now.saturating_sub(time) > breaker.window)
+        {
+            times.pop_front();
+        }
+        times.push_back(now);
+
+        if times.len() < breaker.threshold {
+            return false;
+        }
+        if times.len() > breaker.threshold {
+            return true;
+        }
+        (times.len(), breaker.window)
+    };
+
+    // Reported outside of the locks, in case the reporter fails too.
+    let msg = format!(
+        "circuit breaker tripped: {} failures within {:?}",
+        count, window
+    );
+    let tripped = FailureRecord::new(record.method(), &msg, None, Level::ERROR, false)
+        .with_location(record.location());
+    if filter::enabled(tripped.level(), tripped.location()) {
+        reporter::report(&tripped);
+    }
+    true
+}
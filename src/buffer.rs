@@ -0,0 +1,299 @@
+//! Deferring the failure events of a unit of work until its outcome is known.
+
+use crate::facade::{Level, Span};
+use crate::{context, reporter, sources, FailureRecord};
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::panic::Location;
+use std::time::Duration;
+
+thread_local! {
+    /// The active buffers of the thread, innermost last.
+    static BUFFERS: RefCell<Vec<(u64, Vec<Buffered>)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Collects the failure events of non-panicking methods, such as
+/// [`ok_or_log`](crate::ResultExt::ok_or_log), on the current thread, to emit
+/// them together or not at all once a unit of work is done.
+///
+/// This suits e.g. retried transactions, whose intermediate failures are
+/// only interesting if the whole operation fails. Fatal failures are never
+/// buffered, and buffers nest: the events flushed from an inner buffer go to
+/// the outer one.
+///
+/// Flushed events are the events that would have been logged when the
+/// failures happened, in the span and context they happened in, rather than
+/// those current when the buffer is flushed.
+///
+/// ```
+/// use tracing_unwrap::{LogBuffer, ResultExt};
+///
+/// let buffer = LogBuffer::start();
+/// let saved = (0..3).find_map(|_| "busy".parse::<u32>().ok_or_log());
+/// match saved {
+///     Some(_) => buffer.discard(),
+///     None => buffer.flush(),
+/// }
+/// ```
+///
+/// A buffer that is dropped without being flushed or discarded, e.g. by a
+/// panic, is flushed.
+pub struct LogBuffer {
+    id: u64,
+    /// Buffers are per thread, so they must stay on their thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl LogBuffer {
+    /// Starts buffering the failure events of the current thread.
+    pub fn start() -> Self {
+        let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+        BUFFERS.with(|buffers| buffers.borrow_mut().push((id, Vec::new())));
+        LogBuffer {
+            id,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// The number of failure events buffered so far.
+    pub fn len(&self) -> usize {
+        BUFFERS.with(|buffers| {
+            buffers
+                .borrow()
+                .iter()
+                .find(|(id, _)| *id == self.id)
+                .map_or(0, |(_, buffered)| buffered.len())
+        })
+    }
+
+    /// Whether no failure events were buffered so far.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stops buffering, emitting the buffered events in order.
+    pub fn flush(self) {
+        // Dropping flushes.
+    }
+
+    /// Stops buffering, dropping the buffered events.
+    pub fn discard(self) {
+        self.take();
+        std::mem::forget(self);
+    }
+
+    fn take(&self) -> Vec<Buffered> {
+        BUFFERS.with(|buffers| {
+            let mut buffers = buffers.borrow_mut();
+            match buffers.iter().position(|(id, _)| *id == self.id) {
+                Some(index) => buffers.remove(index).1,
+                None => Vec::new(),
+            }
+        })
+    }
+}
+
+impl Drop for LogBuffer {
+    fn drop(&mut self) {
+        for buffered in self.take() {
+            buffered.replay();
+        }
+    }
+}
+
+impl std::fmt::Debug for LogBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogBuffer")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A failure record, with everything borrowed by it copied, and the context
+/// and span it happened in.
+struct Buffered {
+    method: &'static str,
+    message: String,
+    value: Option<Rendered>,
+    level: Level,
+    location: &'static Location<'static>,
+    during_unwind: bool,
+    pattern: Option<String>,
+    condition: Option<String>,
+    expression: Option<String>,
+    error: Option<OwnedError>,
+    error_type: Option<&'static str>,
+    attempt: Option<u32>,
+    retry_delay: Option<Duration>,
+    address: Option<String>,
+    error_kind: Option<io::ErrorKind>,
+    occurrence: Option<u64>,
+    stacktrace: Option<String>,
+    context: Option<String>,
+    span: Span,
+}
+
+impl Buffered {
+    fn new(record: &FailureRecord<'_>) -> Self {
+        Buffered {
+            method: record.method(),
+            message: record.message().to_owned(),
+            value: record.value().map(|value| Rendered(format!("{:?}", value))),
+            level: record.level(),
+            location: record.location(),
+            during_unwind: record.is_during_unwind(),
+            pattern: record.pattern().map(String::from),
+            condition: record.condition().map(String::from),
+            expression: record.expression().map(String::from),
+            error: record.error().map(OwnedError::new),
+            error_type: record.error_type(),
+            attempt: record.attempt(),
+            retry_delay: record.retry_delay(),
+            address: record.address().map(String::from),
+            error_kind: record.error_kind(),
+            occurrence: record.occurrence(),
+            stacktrace: stacktrace(record),
+            context: context::current(),
+            span: Span::current(),
+        }
+    }
+
+    /// Reports the failure as it would have been when it was buffered, or
+    /// buffers it in the enclosing buffer, if any.
+    fn replay(self) {
+        let mut record = FailureRecord::new(self.method, &self.message, None, self.level, false)
+            .with_location(self.location)
+            .with_during_unwind(self.during_unwind);
+        if let Some(value) = &self.value {
+            record = record.with_value(value);
+        }
+        if let Some(pattern) = &self.pattern {
+            record = record.with_pattern(pattern);
+        }
+        if let Some(condition) = &self.condition {
+            record = record.with_condition(condition);
+        }
+        if let Some(expression) = &self.expression {
+            record = record.with_expression(expression);
+        }
+        if let Some(error) = &self.error {
+            record = record.with_error(error);
+        }
+        if let Some(error_type) = self.error_type {
+            record = record.with_error_type(error_type);
+        }
+        if let Some(attempt) = self.attempt {
+            record = record.with_attempt(attempt, self.retry_delay);
+        }
+        if let Some(address) = &self.address {
+            record = record.with_address(address);
+        }
+        if let Some(kind) = self.error_kind {
+            record = record.with_error_kind(kind);
+        }
+        if let Some(occurrence) = self.occurrence {
+            record = record.with_occurrence(occurrence);
+        }
+        if let Some(stacktrace) = &self.stacktrace {
+            record = record.with_stacktrace(stacktrace);
+        }
+
+        self.span.in_scope(|| {
+            context::replaced(self.context.clone(), || {
+                if !capture(&record) {
+                    reporter::report(&record);
+                }
+            })
+        });
+    }
+}
+
+/// A value rendered with its `Debug` implementation when it was buffered.
+struct Rendered(String);
+
+impl fmt::Debug for Rendered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An error rendered when it was buffered, with as many of its sources as are
+/// logged.
+#[derive(Debug)]
+struct OwnedError {
+    message: String,
+    source: Option<Box<OwnedError>>,
+}
+
+impl OwnedError {
+    fn new(error: &(dyn Error + 'static)) -> Self {
+        // One source more than the maximum depth, so that the chain is
+        // still marked as truncated if it was.
+        Self::with_depth(error, sources::max_depth() + 1)
+    }
+
+    fn with_depth(error: &dyn Error, depth: usize) -> Self {
+        OwnedError {
+            message: error.to_string(),
+            source: error
+                .source()
+                .filter(|_| depth > 0)
+                .map(|source| Box::new(Self::with_depth(source, depth - 1))),
+        }
+    }
+}
+
+impl fmt::Display for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for OwnedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+/// The backtrace to log with a buffered failure: the one it already carries
+/// or that its error provides, or else one captured now, where it happened.
+fn stacktrace(record: &FailureRecord<'_>) -> Option<String> {
+    if let Some(stacktrace) = record.stacktrace() {
+        return Some(stacktrace.to_owned());
+    }
+    #[cfg(feature = "nightly")]
+    if let Some(provided) = record.error().and_then(crate::provide::backtrace) {
+        return Some(provided);
+    }
+    #[cfg(feature = "backtrace")]
+    return crate::stacktrace::capture();
+    #[cfg(not(feature = "backtrace"))]
+    None
+}
+
+/// Buffers a non-fatal failure if the thread has an active buffer, returning
+/// whether it did.
+pub(crate) fn capture(record: &FailureRecord<'_>) -> bool {
+    if record.is_fatal() {
+        return false;
+    }
+
+    if BUFFERS.with(|buffers| buffers.borrow().is_empty()) {
+        return false;
+    }
+
+    // Copied outside of the thread-local, so that its frames don't end up in
+    // the backtrace.
+    let failure = Buffered::new(record);
+    BUFFERS.with(|buffers| match buffers.borrow_mut().last_mut() {
+        Some((_, buffered)) => {
+            buffered.push(failure);
+            true
+        }
+        None => false,
+    })
+}
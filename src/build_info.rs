@@ -0,0 +1,84 @@
+//! Metadata about the running build, stamped onto every failure event.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Identifies the build a failure happened in, so that crash logs can be
+/// attributed to it.
+///
+/// ```
+/// use tracing_unwrap::BuildMetadata;
+///
+/// tracing_unwrap::set_build_metadata(
+///     BuildMetadata::new()
+///         .with_commit("9fceb02")
+///         .with_profile(if cfg!(debug_assertions) { "debug" } else { "release" }),
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildMetadata {
+    commit: Option<String>,
+    profile: Option<String>,
+    timestamp: Option<String>,
+}
+
+impl BuildMetadata {
+    /// Creates empty metadata.
+    pub fn new() -> Self {
+        BuildMetadata::default()
+    }
+
+    /// Sets the version control commit the build was made from, recorded as
+    /// the `unwrap.build.commit` field.
+    pub fn with_commit(self, commit: impl Into<String>) -> Self {
+        BuildMetadata {
+            commit: Some(commit.into()),
+            ..self
+        }
+    }
+
+    /// Sets the build profile, recorded as the `unwrap.build.profile` field.
+    pub fn with_profile(self, profile: impl Into<String>) -> Self {
+        BuildMetadata {
+            profile: Some(profile.into()),
+            ..self
+        }
+    }
+
+    /// Sets when the build was made, recorded as the `unwrap.build.timestamp`
+    /// field.
+    pub fn with_timestamp(self, timestamp: impl Into<String>) -> Self {
+        BuildMetadata {
+            timestamp: Some(timestamp.into()),
+            ..self
+        }
+    }
+
+    pub(crate) fn commit(&self) -> Option<&str> {
+        self.commit.as_deref()
+    }
+
+    pub(crate) fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub(crate) fn timestamp(&self) -> Option<&str> {
+        self.timestamp.as_deref()
+    }
+}
+
+static BUILD_METADATA: RwLock<Option<Arc<BuildMetadata>>> = RwLock::new(None);
+
+/// Registers the metadata of the running build, typically once at startup,
+/// to be stamped onto every failure event.
+pub fn set_build_metadata(metadata: BuildMetadata) {
+    *BUILD_METADATA
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(metadata));
+}
+
+pub(crate) fn current() -> Option<Arc<BuildMetadata>> {
+    BUILD_METADATA
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
@@ -0,0 +1,117 @@
+//! Per-callsite bookkeeping: the inventory of callsites, and the failure
+//! history that some policies depend on.
+
+use core::panic::Location;
+#[cfg(feature = "std")]
+use {
+    crate::{escalation, FailureRecord},
+    std::collections::HashMap,
+    std::sync::{Mutex, PoisonError},
+};
+
+#[cfg(feature = "std")]
+static FAILURES: Mutex<Option<HashMap<&'static Location<'static>, u64>>> = Mutex::new(None);
+
+/// Counts a failure at `location`, returning how many times it has failed so
+/// far, including this time.
+#[cfg(feature = "std")]
+pub(crate) fn count_failure(location: &'static Location<'static>) -> u64 {
+    let mut failures = FAILURES.lock().unwrap_or_else(PoisonError::into_inner);
+    let count = failures
+        .get_or_insert_with(HashMap::new)
+        .entry(location)
+        .or_insert(0);
+    *count += 1;
+    *count
+}
+
+/// Counts a failure at its callsite, recording the count on the record, when
+/// failures are tracked per callsite: with the `callsite-inventory` feature,
+/// or while an escalation policy is installed.
+#[cfg(feature = "std")]
+pub(crate) fn track<'a>(record: &FailureRecord<'a>) -> FailureRecord<'a> {
+    if cfg!(feature = "callsite-inventory") || escalation::is_installed() {
+        record.with_occurrence(count_failure(record.location()))
+    } else {
+        *record
+    }
+}
+
+/// Hashes a callsite with 64-bit FNV-1a, which, unlike the standard library's
+/// hashers, is fixed and stays stable across builds.
+pub(crate) fn id(location: &Location<'_>, method: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let line = location.line().to_le_bytes();
+    let parts: [&[u8]; 3] = [location.file().as_bytes(), &line, method.as_bytes()];
+    parts.iter().fold(OFFSET_BASIS, |hash, part| {
+        // A separator keeps e.g. ("ab", "c") and ("a", "bc") apart.
+        part.iter().chain(&[0xff]).fold(hash, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    })
+}
+
+/// A place in the program that calls one of this crate's methods or macros.
+#[cfg(feature = "callsite-inventory")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Callsite {
+    location: &'static Location<'static>,
+    method: &'static str,
+}
+
+#[cfg(feature = "callsite-inventory")]
+impl Callsite {
+    /// The location of the call.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The method or macro called, e.g. `"Result::unwrap_or_log"`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+}
+
+#[cfg(feature = "callsite-inventory")]
+static INVENTORY: std::sync::RwLock<std::collections::BTreeSet<Callsite>> =
+    std::sync::RwLock::new(std::collections::BTreeSet::new());
+
+/// Returns every callsite that has run so far, sorted by location.
+///
+/// Callsites register themselves the first time they run, whether or not they
+/// fail, so calling this after exercising a program (e.g. at the end of an
+/// integration test suite) lists the places where it is allowed to die.
+#[cfg(feature = "callsite-inventory")]
+pub fn callsites() -> Vec<Callsite> {
+    let inventory = INVENTORY.read().unwrap_or_else(PoisonError::into_inner);
+    inventory.iter().copied().collect()
+}
+
+/// Adds the caller to the inventory of callsites, with the `callsite-inventory`
+/// feature. Otherwise, does nothing.
+#[inline(always)]
+#[track_caller]
+pub(crate) fn register(method: &'static str) {
+    #[cfg(feature = "callsite-inventory")]
+    {
+        let callsite = Callsite {
+            location: Location::caller(),
+            method,
+        };
+        if !INVENTORY
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&callsite)
+        {
+            INVENTORY
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(callsite);
+        }
+    }
+
+    #[cfg(not(feature = "callsite-inventory"))]
+    let _ = method;
+}
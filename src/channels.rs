@@ -0,0 +1,195 @@
+//! Extensions for the channels of tokio's `sync` module, which log
+//! back-pressure and disconnection distinctly.
+
+use crate::{callsite, emit, fail, recovery, span_capture, FailureRecord};
+use std::future::Future;
+use std::panic::Location;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{broadcast, mpsc, watch};
+
+/// Extension trait for [`broadcast::Receiver`]s.
+pub trait BroadcastReceiverExt<T> {
+    /// Receives the next value, like [`broadcast::Receiver::recv`], but skips
+    /// over the values that a lagging receiver missed, logging how many there
+    /// were to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// Yields `None` once every sender is dropped, which is not logged.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn recv_or_log(&mut self) -> impl Future<Output = Option<T>> + '_;
+}
+
+impl<T: Clone> BroadcastReceiverExt<T> for broadcast::Receiver<T> {
+    #[track_caller]
+    fn recv_or_log(&mut self) -> impl Future<Output = Option<T>> + '_ {
+        callsite::register("broadcast::Receiver::recv_or_log");
+        let location = Location::caller();
+        let span = span_capture::capture();
+        async move {
+            loop {
+                match self.recv().await {
+                    Ok(value) => {
+                        recovery::succeeded("broadcast::Receiver::recv_or_log", location);
+                        return Some(value);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        let _span = span_capture::enter(&span);
+                        lagged(skipped, location)
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        }
+    }
+}
+
+/// Extension trait for [`watch::Receiver`]s.
+pub trait WatchReceiverExt {
+    /// Waits for a change, like [`watch::Receiver::changed`], yielding
+    /// whether there was one.
+    ///
+    /// If the sender was dropped instead, logs it to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level and yields `false`.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn changed_or_log(&mut self) -> impl Future<Output = bool> + '_;
+}
+
+impl<T> WatchReceiverExt for watch::Receiver<T> {
+    #[track_caller]
+    fn changed_or_log(&mut self) -> impl Future<Output = bool> + '_ {
+        callsite::register("watch::Receiver::changed_or_log");
+        let location = Location::caller();
+        let span = span_capture::capture();
+        async move {
+            match self.changed().await {
+                Ok(()) => {
+                    recovery::succeeded("watch::Receiver::changed_or_log", location);
+                    true
+                }
+                Err(_) => {
+                    let _span = span_capture::enter(&span);
+                    rejected(
+                        "watch::Receiver::changed_or_log",
+                        "called `watch::Receiver::changed_or_log()` after the sender was dropped",
+                        None,
+                        crate::facade::Level::WARN,
+                        location,
+                    );
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Extension trait for bounded [`mpsc::Sender`]s.
+pub trait MpscSenderExt<T> {
+    /// Sends a value, waiting for capacity, like [`mpsc::Sender::send`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver was dropped, logging it to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn send_or_log(&self, value: T) -> impl Future<Output = ()> + '_;
+
+    /// Attempts to send a value without waiting, like
+    /// [`mpsc::Sender::try_send`], handing it back if it wasn't sent.
+    ///
+    /// A full channel is logged to a [`tracing::Subscriber`] at a [`WARN`]
+    /// level, with its capacity, and a dropped receiver at an [`ERROR`]
+    /// level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn try_send_or_log(&self, value: T) -> Result<(), T>;
+}
+
+impl<T> MpscSenderExt<T> for mpsc::Sender<T> {
+    #[track_caller]
+    fn send_or_log(&self, value: T) -> impl Future<Output = ()> + '_ {
+        callsite::register("mpsc::Sender::send_or_log");
+        let location = Location::caller();
+        let span = span_capture::capture();
+        async move {
+            if self.send(value).await.is_err() {
+                let _span = span_capture::enter(&span);
+                disconnected(
+                    "mpsc::Sender::send_or_log",
+                    "called `mpsc::Sender::send_or_log()` after the receiver was dropped",
+                    location,
+                );
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn try_send_or_log(&self, value: T) -> Result<(), T> {
+        callsite::register("mpsc::Sender::try_send_or_log");
+        match self.try_send(value) {
+            Ok(()) => {
+                recovery::succeeded("mpsc::Sender::try_send_or_log", Location::caller());
+                Ok(())
+            }
+            Err(TrySendError::Full(value)) => {
+                let capacity = format_args!("capacity {}", self.max_capacity());
+                rejected(
+                    "mpsc::Sender::try_send_or_log",
+                    "called `mpsc::Sender::try_send_or_log()` on a full channel",
+                    Some(&capacity),
+                    crate::facade::Level::WARN,
+                    Location::caller(),
+                );
+                Err(value)
+            }
+            Err(TrySendError::Closed(value)) => {
+                rejected(
+                    "mpsc::Sender::try_send_or_log",
+                    "called `mpsc::Sender::try_send_or_log()` after the receiver was dropped",
+                    None,
+                    crate::facade::Level::ERROR,
+                    Location::caller(),
+                );
+                Err(value)
+            }
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn lagged(skipped: u64, location: &'static Location<'static>) {
+    let skipped = format_args!("skipped {} messages", skipped);
+    let record = FailureRecord::new(
+        "broadcast::Receiver::recv_or_log",
+        "receiver lagged behind the `broadcast` channel",
+        Some(&skipped),
+        crate::facade::Level::WARN,
+        false,
+    );
+    emit(&record.with_location(location));
+}
+
+/// Logs a failed channel operation that isn't fatal.
+#[inline(never)]
+#[cold]
+fn rejected(
+    method: &'static str,
+    msg: &str,
+    value: Option<&dyn std::fmt::Debug>,
+    level: crate::facade::Level,
+    location: &'static Location<'static>,
+) {
+    let record = FailureRecord::new(method, msg, value, level, false);
+    emit(&record.with_location(location));
+}
+
+#[inline(never)]
+#[cold]
+fn disconnected(method: &'static str, msg: &str, location: &'static Location<'static>) -> ! {
+    let record = FailureRecord::new(method, msg, None, crate::facade::Level::ERROR, true);
+    fail(&record.with_location(location))
+}
@@ -0,0 +1,114 @@
+//! Extensions for converting between characters, digits and bytes.
+
+use crate::{callsite, failed_with};
+
+/// Extension trait for `char`.
+pub trait CharExt {
+    /// Converts a character to a digit in the given radix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character is not a digit in `radix`, logging the
+    /// character and the radix to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level. Like [`char::to_digit`], also panics if `radix` is not in the
+    /// range `2..=36`.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn to_digit_or_log(self, radix: u32) -> u32;
+
+    /// Converts an ASCII character to its byte value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the character is not ASCII, logging the character to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn to_ascii_or_log(self) -> u8;
+}
+
+impl CharExt for char {
+    #[inline]
+    #[track_caller]
+    fn to_digit_or_log(self, radix: u32) -> u32 {
+        callsite::register("char::to_digit_or_log");
+        match self.to_digit(radix) {
+            Some(digit) => digit,
+            None => failed_with(
+                "char::to_digit_or_log",
+                "called `char::to_digit_or_log()` on a non-digit",
+                &format_args!("char {:?}, radix {}", self, radix),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn to_ascii_or_log(self) -> u8 {
+        callsite::register("char::to_ascii_or_log");
+        if self.is_ascii() {
+            self as u8
+        } else {
+            failed_with(
+                "char::to_ascii_or_log",
+                "called `char::to_ascii_or_log()` on a non-ASCII character",
+                &format_args!("char {:?}", self),
+            )
+        }
+    }
+}
+
+/// Extension trait for converting `u32` values to `char`.
+pub trait U32CharExt {
+    /// Converts a digit in the given radix to a character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not a valid digit in `radix`, logging the
+    /// number and the radix to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level. Like [`char::from_digit`], also panics if `radix` is greater
+    /// than 36.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn to_digit_char_or_log(self, radix: u32) -> char;
+
+    /// Converts a Unicode scalar value to a character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is not a valid Unicode scalar value, logging the
+    /// number to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn to_char_or_log(self) -> char;
+}
+
+impl U32CharExt for u32 {
+    #[inline]
+    #[track_caller]
+    fn to_digit_char_or_log(self, radix: u32) -> char {
+        callsite::register("u32::to_digit_char_or_log");
+        match char::from_digit(self, radix) {
+            Some(c) => c,
+            None => failed_with(
+                "u32::to_digit_char_or_log",
+                "called `u32::to_digit_char_or_log()` on a non-digit",
+                &format_args!("number {}, radix {}", self, radix),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn to_char_or_log(self) -> char {
+        callsite::register("u32::to_char_or_log");
+        match char::from_u32(self) {
+            Some(c) => c,
+            None => failed_with(
+                "u32::to_char_or_log",
+                "called `u32::to_char_or_log()` on an invalid scalar value",
+                &format_args!("number {:#x}", self),
+            ),
+        }
+    }
+}
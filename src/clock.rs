@@ -0,0 +1,66 @@
+//! The time source of the policies that count failures within time windows.
+
+use alloc::boxed::Box;
+use core::time::Duration;
+#[cfg(not(feature = "std"))]
+use once_cell::race::OnceBox;
+#[cfg(feature = "std")]
+use std::{
+    sync::{OnceLock, PoisonError, RwLock},
+    time::Instant,
+};
+
+/// A monotonic source of time.
+///
+/// Replace the default [`SystemClock`] with [`set_clock`] to drive time
+/// deterministically in tests, or, without the standard library, to supply
+/// the tick source of the target.
+pub trait Clock: Send + Sync {
+    /// The time elapsed since an arbitrary, fixed origin. It must never
+    /// decrease.
+    fn now(&self) -> Duration;
+}
+
+/// The default clock, which reads [`Instant::now`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed()
+    }
+}
+
+#[cfg(feature = "std")]
+static CLOCK: RwLock<Option<Box<dyn Clock>>> = RwLock::new(None);
+
+#[cfg(not(feature = "std"))]
+static CLOCK: OnceBox<Box<dyn Clock>> = OnceBox::new();
+
+/// Sets the clock used to count failures within time windows, e.g. by a
+/// [`CircuitBreaker`](crate::CircuitBreaker), replacing the [`SystemClock`].
+/// Pass `None` to restore it.
+///
+/// Without the standard library, there is no default clock, and the clock
+/// can only be set once: later calls are ignored.
+pub fn set_clock(clock: Option<Box<dyn Clock>>) {
+    #[cfg(feature = "std")]
+    {
+        *CLOCK.write().unwrap_or_else(PoisonError::into_inner) = clock;
+    }
+    #[cfg(not(feature = "std"))]
+    if let Some(clock) = clock {
+        let _ = CLOCK.set(Box::new(clock));
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn now() -> Duration {
+    match &*CLOCK.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(clock) => clock.now(),
+        None => SystemClock.now(),
+    }
+}
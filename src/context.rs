@@ -0,0 +1,99 @@
+//! Context strings attached to the failures that happen within a scope, as
+//! set up by `#[unwrap_context]`.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    static CONTEXTS: RefCell<Vec<Cow<'static, str>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes its context from the current thread when dropped.
+pub struct ContextGuard {
+    /// Contexts are per thread, so the guard must stay on its thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ContextGuard {
+    fn exit(self) -> Cow<'static, str> {
+        let context = pop();
+        std::mem::forget(self);
+        context
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        pop();
+    }
+}
+
+fn pop() -> Cow<'static, str> {
+    CONTEXTS
+        .with(|contexts| contexts.borrow_mut().pop())
+        .unwrap_or_default()
+}
+
+/// Attaches `context` to the failures on this thread until the returned
+/// guard is dropped.
+pub fn enter(context: Cow<'static, str>) -> ContextGuard {
+    CONTEXTS.with(|contexts| contexts.borrow_mut().push(context));
+    ContextGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// Attaches `context` to the failures in a future, whenever it is polled.
+pub fn in_context<F: Future>(context: Cow<'static, str>, future: F) -> InContext<F> {
+    InContext {
+        context,
+        future: Box::pin(future),
+    }
+}
+
+/// A future with a context attached, returned by [`in_context`].
+pub struct InContext<F> {
+    context: Cow<'static, str>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for InContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.get_mut();
+        let guard = enter(std::mem::take(&mut this.context));
+        let poll = this.future.as_mut().poll(cx);
+        this.context = guard.exit();
+        poll
+    }
+}
+
+/// Returns the contexts of the current thread, outermost first, if any.
+pub(crate) fn current() -> Option<String> {
+    CONTEXTS.with(|contexts| {
+        let contexts = contexts.borrow();
+        (!contexts.is_empty()).then(|| contexts.join(": "))
+    })
+}
+
+/// Runs `f` with `context` in place of the contexts of the current thread, to
+/// replay a failure in the context it happened in.
+pub(crate) fn replaced<R>(context: Option<String>, f: impl FnOnce() -> R) -> R {
+    /// Puts the contexts of the thread back, even if `f` panics.
+    struct Restore(Vec<Cow<'static, str>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CONTEXTS.with(|contexts| *contexts.borrow_mut() = std::mem::take(&mut self.0));
+        }
+    }
+
+    let replaced = context.map(Cow::Owned).into_iter().collect();
+    let _restore = Restore(CONTEXTS.with(|contexts| contexts.replace(replaced)));
+    f()
+}
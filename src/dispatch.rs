@@ -0,0 +1,50 @@
+//! Process-wide override of the dispatcher that receives failure events.
+
+#[cfg(feature = "std")]
+use {
+    crate::facade::Dispatch,
+    std::sync::{PoisonError, RwLock},
+};
+
+#[cfg(feature = "std")]
+static DISPATCH: RwLock<Option<Dispatch>> = RwLock::new(None);
+
+/// Sets the dispatcher that every failure event is sent to, instead of the
+/// current default one. Pass `None` to restore the default.
+///
+/// This lets a plugin or library that maintains its own isolated subscriber
+/// receive the failures, whichever subscriber the host application installed.
+/// To redirect the failures of a single thread for a while, use
+/// [`tracing::dispatcher::with_default`] instead.
+///
+/// ```
+/// use tracing::Dispatch;
+///
+/// let dispatch = Dispatch::new(tracing::subscriber::NoSubscriber::default());
+/// tracing_unwrap::set_dispatch(Some(dispatch));
+/// ```
+#[cfg(feature = "std")]
+pub fn set_dispatch(dispatch: Option<tracing::Dispatch>) {
+    *DISPATCH.write().unwrap_or_else(PoisonError::into_inner) = dispatch;
+}
+
+/// Runs `f`, which emits a failure event, with the configured dispatcher as
+/// the default, if there is one.
+#[cfg(feature = "std")]
+pub(crate) fn with_dispatch<R>(f: impl FnOnce() -> R) -> R {
+    let dispatch = DISPATCH
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    match dispatch {
+        Some(dispatch) => crate::facade::with_default(&dispatch, f),
+        None => f(),
+    }
+}
+
+/// Runs `f`, which emits a failure event. Without the standard library, the
+/// dispatcher can't be configured.
+#[cfg(not(feature = "std"))]
+pub(crate) fn with_dispatch<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
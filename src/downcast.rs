@@ -0,0 +1,86 @@
+//! Extensions for downcasting boxed [`Error`] trait objects.
+
+use crate::{callsite, fail, FailureRecord};
+use alloc::boxed::Box;
+use core::any::type_name;
+use core::error::Error;
+
+/// Extension trait for boxed [`Error`] trait objects.
+pub trait BoxErrorExt {
+    /// Attempts to downcast the boxed error to a concrete type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the error is not an `E`, logging the expected type and the
+    /// original error's `Display` and `Debug` renderings to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn downcast_or_log<E: Error + 'static>(self) -> Box<E>;
+
+    /// Returns a reference to the inner error as a concrete type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the error is not an `E`, logging the expected type and the
+    /// original error's `Display` and `Debug` renderings to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn downcast_ref_or_log<E: Error + 'static>(&self) -> &E;
+}
+
+macro_rules! impl_box_error_ext {
+    ($($ty:ty),+) => {$(
+        impl BoxErrorExt for Box<$ty> {
+            #[inline]
+            #[track_caller]
+            fn downcast_or_log<E: Error + 'static>(self) -> Box<E> {
+                callsite::register("Box<dyn Error>::downcast_or_log");
+                match self.downcast::<E>() {
+                    Ok(e) => e,
+                    Err(original) => mismatched("Box<dyn Error>::downcast_or_log",
+                        "called `Box<dyn Error>::downcast_or_log()` on a different error type",
+                        type_name::<E>(),
+                        &*original,
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn downcast_ref_or_log<E: Error + 'static>(&self) -> &E {
+                callsite::register("Box<dyn Error>::downcast_ref_or_log");
+                match self.downcast_ref::<E>() {
+                    Some(e) => e,
+                    None => mismatched("Box<dyn Error>::downcast_ref_or_log",
+                        "called `Box<dyn Error>::downcast_ref_or_log()` on a different error type",
+                        type_name::<E>(),
+                        &**self,
+                    ),
+                }
+            }
+        }
+    )+};
+}
+
+impl_box_error_ext!(dyn Error, dyn Error + Send, dyn Error + Send + Sync);
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn mismatched(
+    method: &'static str,
+    msg: &str,
+    expected: &str,
+    original: &(dyn Error + 'static),
+) -> ! {
+    let value = format_args!(
+        "expected `{}`, found {} ({:?})",
+        expected, original, original
+    );
+    fail(
+        &FailureRecord::new(method, msg, Some(&value), crate::facade::Level::ERROR, true)
+            .with_error(original),
+    )
+}
@@ -0,0 +1,122 @@
+//! Extensions for Result types whose error implements [`Error`].
+
+use crate::facade::Level;
+use crate::{callsite, emit, fail, levels, recovery, FailureRecord};
+use core::error::Error;
+use core::panic::Location;
+
+/// Extension trait for Result types whose error implements [`Error`].
+///
+/// Unlike the methods of [`ResultExt`](crate::ResultExt), which only know the
+/// error as a [`Debug`](core::fmt::Debug) value, these log its source chain as
+/// the `unwrap.sources` field and, with the `structured-errors` feature,
+/// record it as the `error` field through `tracing`'s support for error
+/// values.
+pub trait ErrorResultExt<T, E> {
+    /// Converts `self` into an [`Option<T>`], consuming `self`, and logs the
+    /// error, if any, to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn ok_or_log_error(self) -> Option<T>;
+
+    /// Unwraps a result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging a message provided by the
+    /// [`Err`]'s value to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log_error(self) -> T;
+
+    /// Unwraps a result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging the passed message and the
+    /// content of the [`Err`] to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log_error(self, msg: &str) -> T;
+}
+
+impl<T, E: Error + 'static> ErrorResultExt<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn ok_or_log_error(self) -> Option<T> {
+        callsite::register("Result::ok_or_log_error");
+        match self {
+            Ok(t) => {
+                recovery::succeeded("Result::ok_or_log_error", Location::caller());
+                Some(t)
+            }
+            Err(e) => {
+                discarded_error(
+                    levels::declared(&e),
+                    "Result::ok_or_log_error",
+                    "called `Result::ok_or_log_error` on an `Err` value",
+                    &e,
+                );
+                None
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_error(self) -> T {
+        callsite::register("Result::unwrap_or_log_error");
+        match self {
+            Ok(t) => t,
+            Err(e) => failed_error(
+                levels::declared(&e),
+                "Result::unwrap_or_log_error",
+                "called `Result::unwrap_or_log_error()` on an `Err` value",
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_error(self, msg: &str) -> T {
+        callsite::register("Result::expect_or_log_error");
+        match self {
+            Ok(t) => t,
+            Err(e) => failed_error(levels::declared(&e), "Result::expect_or_log_error", msg, &e),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn failed_error(
+    declared: Option<Level>,
+    method: &'static str,
+    msg: &str,
+    error: &(dyn Error + 'static),
+) -> ! {
+    let record = FailureRecord::new(method, msg, Some(error), Level::ERROR, true).with_error(error);
+    match declared {
+        Some(level) => fail(&record.with_level(level).with_caller_level()),
+        None => fail(&record),
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn discarded_error(
+    declared: Option<Level>,
+    method: &'static str,
+    msg: &str,
+    error: &(dyn Error + 'static),
+) {
+    let record = FailureRecord::new(method, msg, Some(error), Level::WARN, false).with_error(error);
+    match declared {
+        Some(level) => emit(&record.with_level(level).with_caller_level()),
+        None => emit(&record),
+    };
+}
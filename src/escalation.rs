@@ -0,0 +1,94 @@
+//! Raising the level of failures that keep recurring at the same callsite.
+
+use crate::callsite;
+use crate::facade::Level;
+use crate::FailureRecord;
+use std::sync::{PoisonError, RwLock};
+
+static ESCALATION: RwLock<Option<Escalation>> = RwLock::new(None);
+
+/// A policy that picks the level of a failure from how many times its
+/// callsite has failed, so that transient blips are logged quietly and
+/// persistent problems loudly.
+///
+/// ```
+/// use tracing::Level;
+/// use tracing_unwrap::Escalation;
+///
+/// // The first 3 failures at each callsite are logged at DEBUG, the next 7
+/// // at WARN, and any further ones at ERROR.
+/// let policy = Escalation::new(Level::ERROR)
+///     .with_threshold(3, Level::DEBUG)
+///     .with_threshold(10, Level::WARN);
+/// tracing_unwrap::set_escalation(Some(policy));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Escalation {
+    /// Sorted by ascending occurrence count.
+    thresholds: Vec<(u64, Level)>,
+    escalated: Level,
+}
+
+impl Escalation {
+    /// Creates a policy that logs every failure at `escalated`, until
+    /// thresholds are added.
+    pub fn new(escalated: tracing::Level) -> Self {
+        Escalation {
+            thresholds: Vec::new(),
+            escalated,
+        }
+    }
+
+    /// Creates a policy that logs the first `n` failures at each callsite at
+    /// [`WARN`](Level::WARN), and any further ones at [`ERROR`](Level::ERROR).
+    pub fn after(n: u64) -> Self {
+        Escalation::new(Level::ERROR).with_threshold(n, Level::WARN)
+    }
+
+    /// Logs failures at `level` until their callsite has failed `n` times,
+    /// unless a lower threshold applies.
+    pub fn with_threshold(mut self, n: u64, level: tracing::Level) -> Self {
+        self.thresholds.retain(|&(existing, _)| existing != n);
+        self.thresholds.push((n, level));
+        self.thresholds.sort_by_key(|&(n, _)| n);
+        self
+    }
+
+    /// Returns the level for the `occurrence`-th failure at a callsite,
+    /// counting from 1.
+    pub fn level_for(&self, occurrence: u64) -> tracing::Level {
+        self.thresholds
+            .iter()
+            .find(|&&(n, _)| occurrence <= n)
+            .map_or(self.escalated, |&(_, level)| level)
+    }
+}
+
+/// Installs a process-wide [`Escalation`] policy, replacing any previous one.
+/// Pass `None` to log every failure at its usual level.
+pub fn set_escalation(policy: Option<Escalation>) {
+    *ESCALATION.write().unwrap_or_else(PoisonError::into_inner) = policy;
+}
+
+/// Whether a policy is installed, which needs failures to be counted per
+/// callsite.
+pub(crate) fn is_installed() -> bool {
+    ESCALATION
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Applies the installed policy, if any, to `record`.
+pub(crate) fn escalate<'a>(record: &FailureRecord<'a>) -> FailureRecord<'a> {
+    match &*ESCALATION.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(policy) => {
+            let occurrence = match record.occurrence() {
+                Some(occurrence) => occurrence,
+                None => callsite::count_failure(record.location()),
+            };
+            record.with_level(policy.level_for(occurrence))
+        }
+        None => *record,
+    }
+}
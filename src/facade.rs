@@ -0,0 +1,26 @@
+//! The facade through which this crate uses `tracing`.
+//!
+//! Every other module goes through these re-exports rather than naming
+//! `tracing` directly, which keeps the crate's own use of `tracing` in one
+//! place. The public API still takes and returns `tracing` 0.1's `Level`,
+//! `Dispatch` and `Span`, so moving to another major version of `tracing`
+//! would be a breaking release of this crate nonetheless. Public signatures
+//! name those types by their `tracing` paths rather than through here.
+//!
+//! The `tracing-01`/`tracing-02` features that would select the version of
+//! `tracing` here are deferred until `tracing` 0.2 is published; until then
+//! this crate builds against `tracing` 0.1 only.
+
+#[cfg(feature = "std")]
+pub(crate) use tracing::dispatcher::with_default;
+pub(crate) use tracing::event;
+pub(crate) use tracing::field;
+#[cfg(feature = "std")]
+pub(crate) use tracing::level_filters::LevelFilter;
+#[cfg(feature = "std")]
+pub(crate) use tracing::span::Entered;
+#[cfg(feature = "std")]
+pub(crate) use tracing::warn;
+pub use tracing::Level;
+#[cfg(feature = "std")]
+pub use tracing::{Dispatch, Span};
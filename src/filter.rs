@@ -0,0 +1,145 @@
+//! Per-location filtering of failed unwrap events.
+
+use crate::facade::Level;
+#[cfg(feature = "std")]
+use crate::facade::LevelFilter;
+use core::panic::Location;
+#[cfg(feature = "std")]
+use core::str::FromStr;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+#[cfg(feature = "std")]
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// A set of rules deciding which failed unwraps are emitted, based on where
+/// in the source they were called from.
+///
+/// A subscriber's own filter only sees this crate as the origin of every
+/// event, so it can't tell unwraps in one part of a program from another.
+/// A `Filter` is instead checked against the caller's location before the
+/// event is emitted.
+///
+/// Filters are written like [`EnvFilter`] directives: a comma-separated list
+/// of `path=level` rules, plus an optional bare `level` that applies to any
+/// caller not matched by a rule. Each `path` is matched as a prefix of the
+/// caller's source file path, as reported by [`Location::file()`], and the
+/// longest matching prefix wins. An event is emitted if its level is enabled
+/// by the matching `level`, which can also be `off`.
+///
+/// ```
+/// use tracing_unwrap::Filter;
+///
+/// // Only emit failed unwraps (not discarded errors) from `src/net`, and
+/// // nothing at all from `src/net/retry.rs`.
+/// let filter: Filter = "src/net=error,src/net/retry.rs=off".parse().unwrap();
+/// tracing_unwrap::set_filter(filter);
+/// ```
+///
+/// [`EnvFilter`]: https://docs.rs/tracing-subscriber/*/tracing_subscriber/filter/struct.EnvFilter.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Filter {
+    default: LevelFilter,
+    /// Sorted by descending path length, so the first match is the longest.
+    directives: Vec<(String, LevelFilter)>,
+}
+
+#[cfg(feature = "std")]
+impl Filter {
+    /// Creates a filter that applies `default` to every caller.
+    pub fn new(default: tracing::level_filters::LevelFilter) -> Self {
+        Filter {
+            default,
+            directives: Vec::new(),
+        }
+    }
+
+    /// Adds a rule applying `level` to callers whose source file path starts
+    /// with `path`.
+    pub fn with_directive(
+        mut self,
+        path: impl Into<String>,
+        level: tracing::level_filters::LevelFilter,
+    ) -> Self {
+        let path = path.into();
+        self.directives.retain(|(existing, _)| *existing != path);
+        self.directives.push((path, level));
+        self.directives
+            .sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+        self
+    }
+
+    /// Returns whether an event at `level` from `location` should be emitted.
+    pub fn enabled(&self, level: tracing::Level, location: &Location<'_>) -> bool {
+        let file = location.file();
+        let max = self
+            .directives
+            .iter()
+            .find(|(path, _)| file.starts_with(path.as_str()))
+            .map_or(self.default, |(_, level)| *level);
+
+        level <= max
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Filter {
+    /// Returns a filter that emits every failed unwrap.
+    fn default() -> Self {
+        Filter::new(LevelFilter::TRACE)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromStr for Filter {
+    type Err = tracing::level_filters::ParseLevelFilterError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            filter = match directive.rsplit_once('=') {
+                Some((path, level)) => filter.with_directive(path.trim(), level.trim().parse()?),
+                None => Filter {
+                    default: directive.parse()?,
+                    ..filter
+                },
+            };
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Installs a process-wide [`Filter`] for failed unwrap events, replacing any
+/// previous one.
+#[cfg(feature = "std")]
+pub fn set_filter(filter: Filter) {
+    *FILTER.write().unwrap_or_else(PoisonError::into_inner) = Some(filter);
+}
+
+/// Enables or disables every failure event, process-wide, e.g. to silence a
+/// log storm from a known issue without redeploying. Failures still panic as
+/// usual while events are disabled.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns whether events are enabled, and the installed filter, if any,
+/// enables an event at `level` from `location`.
+pub(crate) fn enabled(level: Level, location: &Location<'_>) -> bool {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    #[cfg(feature = "std")]
+    if let Some(filter) = &*FILTER.read().unwrap_or_else(PoisonError::into_inner) {
+        return filter.enabled(level, location);
+    }
+
+    #[cfg(not(feature = "std"))]
+    let _ = (level, location);
+    true
+}
@@ -0,0 +1,131 @@
+//! Extensions for [`ControlFlow`] and [`Poll`], as found in visitors and
+//! manual `poll` implementations.
+
+use crate::{callsite, discarded_with, failed_with, recovery};
+use core::fmt;
+use core::ops::ControlFlow;
+use core::panic::Location;
+use core::task::Poll;
+
+/// Extension trait for [`ControlFlow`]s.
+pub trait ControlFlowExt<B, C> {
+    /// Unwraps a control flow, yielding the content of a
+    /// [`Continue`](ControlFlow::Continue).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Break`](ControlFlow::Break), logging a
+    /// message provided by its value to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn continue_or_log(self) -> C
+    where
+        B: fmt::Debug;
+
+    /// Unwraps a control flow, yielding the content of a
+    /// [`Break`](ControlFlow::Break).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Continue`](ControlFlow::Continue), logging
+    /// a message provided by its value to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn break_or_log(self) -> B
+    where
+        C: fmt::Debug;
+}
+
+impl<B, C> ControlFlowExt<B, C> for ControlFlow<B, C> {
+    #[inline]
+    #[track_caller]
+    fn continue_or_log(self) -> C
+    where
+        B: fmt::Debug,
+    {
+        callsite::register("ControlFlow::continue_or_log");
+        match self {
+            ControlFlow::Continue(c) => c,
+            ControlFlow::Break(b) => failed_with(
+                "ControlFlow::continue_or_log",
+                "called `ControlFlow::continue_or_log()` on a `Break` value",
+                &b,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn break_or_log(self) -> B
+    where
+        C: fmt::Debug,
+    {
+        callsite::register("ControlFlow::break_or_log");
+        match self {
+            ControlFlow::Break(b) => b,
+            ControlFlow::Continue(c) => failed_with(
+                "ControlFlow::break_or_log",
+                "called `ControlFlow::break_or_log()` on a `Continue` value",
+                &c,
+            ),
+        }
+    }
+}
+
+/// Extension trait for [`Poll`]s of [`Result`]s, as returned by manual
+/// `poll` implementations.
+pub trait PollExt<T, E> {
+    /// Unwraps the result of a ready poll, yielding [`Poll::Pending`] for
+    /// [`Poll::Pending`] and the content of the [`Ok`] for `Ready(Ok(_))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Ready(Err(_))`, logging a message provided by
+    /// the [`Err`]'s value to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_ready_or_log(self) -> Poll<T>;
+
+    /// Passes the poll through unchanged, logging the error of a
+    /// `Ready(Err(_))` to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn inspect_err_or_log(self) -> Poll<Result<T, E>>;
+}
+
+impl<T, E: fmt::Debug> PollExt<T, E> for Poll<Result<T, E>> {
+    #[inline]
+    #[track_caller]
+    fn unwrap_ready_or_log(self) -> Poll<T> {
+        callsite::register("Poll::unwrap_ready_or_log");
+        match self {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(t)) => Poll::Ready(t),
+            Poll::Ready(Err(e)) => failed_with(
+                "Poll::unwrap_ready_or_log",
+                "called `Poll::unwrap_ready_or_log()` on a `Ready(Err)` value",
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn inspect_err_or_log(self) -> Poll<Result<T, E>> {
+        callsite::register("Poll::inspect_err_or_log");
+        match &self {
+            Poll::Pending => {}
+            Poll::Ready(Ok(_)) => {
+                recovery::succeeded("Poll::inspect_err_or_log", Location::caller())
+            }
+            Poll::Ready(Err(e)) => discarded_with(
+                "Poll::inspect_err_or_log",
+                "called `Poll::inspect_err_or_log()` on a `Ready(Err)` value",
+                e,
+            ),
+        }
+        self
+    }
+}
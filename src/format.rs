@@ -0,0 +1,90 @@
+//! Process-wide hooks that format failure messages.
+
+use crate::FailureRecord;
+use std::fmt::Write;
+use std::sync::{PoisonError, RwLock};
+
+/// A function producing the message for a failure.
+pub type Formatter = fn(&FailureRecord<'_>) -> String;
+
+static EVENT_FORMATTER: RwLock<Option<Formatter>> = RwLock::new(None);
+static PANIC_FORMATTER: RwLock<Option<Formatter>> = RwLock::new(None);
+
+/// Sets the function that produces the message of every failure event,
+/// replacing the default rendering of a [`FailureRecord`]. Pass `None` to
+/// restore the default.
+///
+/// ```
+/// tracing_unwrap::set_event_formatter(Some(|record| {
+///     format!("[OPS-1234] {}", record)
+/// }));
+/// ```
+pub fn set_event_formatter(formatter: Option<Formatter>) {
+    *EVENT_FORMATTER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = formatter;
+}
+
+/// Sets the function that produces the panic message of every failed unwrap.
+/// Pass `None` to restore the default.
+///
+/// A panic formatter takes precedence over the **`panic-quiet`** feature.
+pub fn set_panic_formatter(formatter: Option<Formatter>) {
+    *PANIC_FORMATTER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = formatter;
+}
+
+/// A [`Formatter`] that renders a failure as a single-line JSON object, for
+/// platforms that only capture the panic line of a crashed process:
+///
+/// ```
+/// tracing_unwrap::set_panic_formatter(Some(tracing_unwrap::json_formatter));
+/// ```
+///
+/// The object has the keys `msg`, the default message; `type`, the method or
+/// macro that failed; `location`, as `file:line:column`; and `callsite_id`,
+/// as in the `unwrap.callsite_id` field.
+pub fn json_formatter(record: &FailureRecord<'_>) -> String {
+    let mut json = String::from("{\"msg\":");
+    push_json_string(&mut json, &record.to_string());
+    json.push_str(",\"type\":");
+    push_json_string(&mut json, record.method());
+    json.push_str(",\"location\":");
+    push_json_string(&mut json, &record.location().to_string());
+    let _ = write!(json, ",\"callsite_id\":\"{:016x}\"}}", record.callsite_id());
+    json
+}
+
+/// Appends `s` to `json` as a JSON string literal.
+fn push_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+pub(crate) fn event_message(record: &FailureRecord<'_>) -> Option<String> {
+    let formatter = *EVENT_FORMATTER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    formatter.map(|f| f(record))
+}
+
+pub(crate) fn panic_message(record: &FailureRecord<'_>) -> Option<String> {
+    let formatter = *PANIC_FORMATTER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    formatter.map(|f| f(record))
+}
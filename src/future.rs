@@ -0,0 +1,334 @@
+//! Extensions for futures that resolve to results and options.
+
+use crate::span_capture::{self, Captured};
+use crate::{callsite, emit, fail, recovery, FailureRecord};
+use core::fmt;
+use core::future::Future;
+use core::panic::Location;
+
+/// Extension trait for futures that resolve to a [`Result`].
+///
+/// Each adapter captures the location of its call when the future is
+/// created, and reports a failure there even though it is detected when the
+/// future is polled, so that the adapters compose with combinator chains as
+/// `fut.await.unwrap_or_log()` doesn't.
+///
+/// The failure is logged in the span that is current when the future is
+/// polled, unless [`set_async_span_capture`](crate::set_async_span_capture)
+/// says otherwise. The `_in_current_span` forms log it in the span that is
+/// current when they are called instead, as executors often poll futures in
+/// an unrelated span.
+pub trait ResultFutureExt<T, E>: Future<Output = Result<T, E>> + Sized {
+    /// Resolves to an [`Option<T>`], logging the error, if any, to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn ok_or_log(self) -> impl Future<Output = Option<T>>
+    where
+        E: fmt::Debug;
+
+    /// Resolves to the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future resolves to an [`Err`], logging a message
+    /// provided by the [`Err`]'s value to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log(self) -> impl Future<Output = T>
+    where
+        E: fmt::Debug;
+
+    /// Resolves to the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future resolves to an [`Err`], logging the passed
+    /// message and the content of the [`Err`] to a [`tracing::Subscriber`] at
+    /// an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log(self, msg: &str) -> impl Future<Output = T>
+    where
+        E: fmt::Debug;
+
+    /// Like [`ok_or_log`](Self::ok_or_log), logging the error in the span
+    /// that is current now rather than when the future is polled.
+    #[cfg(feature = "std")]
+    fn ok_or_log_in_current_span(self) -> impl Future<Output = Option<T>>
+    where
+        E: fmt::Debug;
+
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), logging the error in the
+    /// span that is current now rather than when the future is polled.
+    #[cfg(feature = "std")]
+    fn unwrap_or_log_in_current_span(self) -> impl Future<Output = T>
+    where
+        E: fmt::Debug;
+
+    /// Like [`expect_or_log`](Self::expect_or_log), logging the error in the
+    /// span that is current now rather than when the future is polled.
+    #[cfg(feature = "std")]
+    fn expect_or_log_in_current_span(self, msg: &str) -> impl Future<Output = T>
+    where
+        E: fmt::Debug;
+}
+
+impl<F, T, E> ResultFutureExt<T, E> for F
+where
+    F: Future<Output = Result<T, E>>,
+{
+    #[track_caller]
+    fn ok_or_log(self) -> impl Future<Output = Option<T>>
+    where
+        E: fmt::Debug,
+    {
+        ok_or_log(
+            self,
+            "ResultFuture::ok_or_log",
+            "called `Result::ok_or_log` on an `Err` value",
+            span_capture::capture(),
+        )
+    }
+
+    #[track_caller]
+    fn unwrap_or_log(self) -> impl Future<Output = T>
+    where
+        E: fmt::Debug,
+    {
+        expect_or_log(
+            self,
+            "ResultFuture::unwrap_or_log",
+            "called `Result::unwrap_or_log()` on an `Err` value",
+            span_capture::capture(),
+        )
+    }
+
+    #[track_caller]
+    fn expect_or_log(self, msg: &str) -> impl Future<Output = T>
+    where
+        E: fmt::Debug,
+    {
+        expect_or_log(
+            self,
+            "ResultFuture::expect_or_log",
+            msg,
+            span_capture::capture(),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn ok_or_log_in_current_span(self) -> impl Future<Output = Option<T>>
+    where
+        E: fmt::Debug,
+    {
+        ok_or_log(
+            self,
+            "ResultFuture::ok_or_log_in_current_span",
+            "called `Result::ok_or_log_in_current_span` on an `Err` value",
+            Some(crate::facade::Span::current()),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn unwrap_or_log_in_current_span(self) -> impl Future<Output = T>
+    where
+        E: fmt::Debug,
+    {
+        expect_or_log(
+            self,
+            "ResultFuture::unwrap_or_log_in_current_span",
+            "called `Result::unwrap_or_log_in_current_span()` on an `Err` value",
+            Some(crate::facade::Span::current()),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn expect_or_log_in_current_span(self, msg: &str) -> impl Future<Output = T>
+    where
+        E: fmt::Debug,
+    {
+        expect_or_log(
+            self,
+            "ResultFuture::expect_or_log_in_current_span",
+            msg,
+            Some(crate::facade::Span::current()),
+        )
+    }
+}
+
+/// Extension trait for futures that resolve to an [`Option`].
+///
+/// Like [`ResultFutureExt`], each adapter reports a failure at the location
+/// of its call, and has an `_in_current_span` form.
+pub trait OptionFutureExt<T>: Future<Output = Option<T>> + Sized {
+    /// Resolves to the content of a [`Some`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future resolves to [`None`], logging an error message to
+    /// a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log(self) -> impl Future<Output = T>;
+
+    /// Resolves to the content of a [`Some`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future resolves to [`None`], logging the passed message
+    /// to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log(self, msg: &str) -> impl Future<Output = T>;
+
+    /// Like [`unwrap_or_log`](Self::unwrap_or_log), logging the failure in
+    /// the span that is current now rather than when the future is polled.
+    #[cfg(feature = "std")]
+    fn unwrap_or_log_in_current_span(self) -> impl Future<Output = T>;
+
+    /// Like [`expect_or_log`](Self::expect_or_log), logging the failure in
+    /// the span that is current now rather than when the future is polled.
+    #[cfg(feature = "std")]
+    fn expect_or_log_in_current_span(self, msg: &str) -> impl Future<Output = T>;
+}
+
+impl<F, T> OptionFutureExt<T> for F
+where
+    F: Future<Output = Option<T>>,
+{
+    #[track_caller]
+    fn unwrap_or_log(self) -> impl Future<Output = T> {
+        expect_some_or_log(
+            self,
+            "OptionFuture::unwrap_or_log",
+            "called `Option::unwrap_or_log()` on a `None` value",
+            span_capture::capture(),
+        )
+    }
+
+    #[track_caller]
+    fn expect_or_log(self, msg: &str) -> impl Future<Output = T> {
+        expect_some_or_log(
+            self,
+            "OptionFuture::expect_or_log",
+            msg,
+            span_capture::capture(),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn unwrap_or_log_in_current_span(self) -> impl Future<Output = T> {
+        expect_some_or_log(
+            self,
+            "OptionFuture::unwrap_or_log_in_current_span",
+            "called `Option::unwrap_or_log_in_current_span()` on a `None` value",
+            Some(crate::facade::Span::current()),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn expect_or_log_in_current_span(self, msg: &str) -> impl Future<Output = T> {
+        expect_some_or_log(
+            self,
+            "OptionFuture::expect_or_log_in_current_span",
+            msg,
+            Some(crate::facade::Span::current()),
+        )
+    }
+}
+
+#[track_caller]
+fn ok_or_log<F, T, E>(
+    future: F,
+    method: &'static str,
+    msg: &'static str,
+    span: Captured,
+) -> impl Future<Output = Option<T>>
+where
+    F: Future<Output = Result<T, E>>,
+    E: fmt::Debug,
+{
+    callsite::register(method);
+    let location = Location::caller();
+    async move {
+        match future.await {
+            Ok(t) => {
+                recovery::succeeded(method, location);
+                Some(t)
+            }
+            Err(e) => {
+                let _span = span_capture::enter(&span);
+                let record =
+                    FailureRecord::new(method, msg, Some(&e), crate::facade::Level::WARN, false);
+                emit(&record.with_location(location));
+                None
+            }
+        }
+    }
+}
+
+#[track_caller]
+fn expect_or_log<'a, F, T, E>(
+    future: F,
+    method: &'static str,
+    msg: &'a str,
+    span: Captured,
+) -> impl Future<Output = T> + use<'a, F, T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: fmt::Debug,
+{
+    callsite::register(method);
+    let location = Location::caller();
+    async move {
+        match future.await {
+            Ok(t) => t,
+            Err(e) => {
+                let _span = span_capture::enter(&span);
+                failed(method, msg, Some(&e), location)
+            }
+        }
+    }
+}
+
+#[track_caller]
+fn expect_some_or_log<'a, F, T>(
+    future: F,
+    method: &'static str,
+    msg: &'a str,
+    span: Captured,
+) -> impl Future<Output = T> + use<'a, F, T>
+where
+    F: Future<Output = Option<T>>,
+{
+    callsite::register(method);
+    let location = Location::caller();
+    async move {
+        match future.await {
+            Some(t) => t,
+            None => {
+                let _span = span_capture::enter(&span);
+                failed(method, msg, None, location)
+            }
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn failed(
+    method: &'static str,
+    msg: &str,
+    value: Option<&dyn fmt::Debug>,
+    location: &'static Location<'static>,
+) -> ! {
+    let record = FailureRecord::new(method, msg, value, crate::facade::Level::ERROR, true);
+    fail(&record.with_location(location))
+}
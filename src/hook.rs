@@ -0,0 +1,38 @@
+//! A process-wide hook that every failure runs, for side effects such as
+//! metrics.
+
+use crate::FailureRecord;
+use std::sync::{PoisonError, RwLock};
+
+/// A function that is called with every failure.
+pub type FailureHook = fn(&FailureRecord<'_>);
+
+static HOOK: RwLock<Option<FailureHook>> = RwLock::new(None);
+
+/// Sets a function that every failure runs, after its event is emitted and,
+/// for a fatal failure, before the panic. Pass `None` to remove it.
+///
+/// Unlike a [`FailureReporter`](crate::FailureReporter), the hook also runs
+/// for failures that are filtered out or buffered, so that e.g. a metrics
+/// counter sees every one of them.
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// static FAILURES: AtomicU64 = AtomicU64::new(0);
+///
+/// tracing_unwrap::set_failure_hook(Some(|_record| {
+///     FAILURES.fetch_add(1, Ordering::Relaxed);
+/// }));
+/// ```
+pub fn set_failure_hook(hook: Option<FailureHook>) {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = hook;
+}
+
+/// Runs the failure hook, if one is set.
+pub(crate) fn run(record: &FailureRecord<'_>) {
+    let hook = *HOOK.read().unwrap_or_else(PoisonError::into_inner);
+    if let Some(hook) = hook {
+        hook(record);
+    }
+}
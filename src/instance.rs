@@ -0,0 +1,30 @@
+//! The identity of the process instance, attached to failure events.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+static INSTANCE_ID: RwLock<Option<Arc<str>>> = RwLock::new(None);
+
+/// Sets an identifier of this process instance, e.g. a hostname or a pod
+/// name, to be recorded as the `unwrap.instance_id` field of every failure
+/// event. Pass `None` to stop recording it.
+///
+/// This is meant for fleets that send their logs into a shared store without
+/// enriching them per host.
+pub fn set_instance_id(id: Option<String>) {
+    *INSTANCE_ID.write().unwrap_or_else(PoisonError::into_inner) = id.map(Arc::from);
+}
+
+/// Captures the hostname of the machine, once, and sets it as the instance
+/// identifier with [`set_instance_id`].
+#[cfg(feature = "hostname")]
+pub fn set_instance_id_from_hostname() {
+    let hostname = gethostname::gethostname();
+    set_instance_id(Some(hostname.to_string_lossy().into_owned()));
+}
+
+pub(crate) fn current() -> Option<Arc<str>> {
+    INSTANCE_ID
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
@@ -0,0 +1,89 @@
+//! Adapters for iterators over results.
+
+use crate::{callsite, emit, recovery, FailureRecord};
+use alloc::format;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::panic::Location;
+
+/// Extension trait for iterators over [`Result`]s.
+pub trait ResultIteratorExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Logs each [`Err`] as it passes through to a [`tracing::Subscriber`] at
+    /// a [`WARN`] level, yielding every item unchanged, so that whatever
+    /// consumes the iterator still sees the errors.
+    ///
+    /// The location of this call is reported for the errors.
+    ///
+    /// ```
+    /// use tracing_unwrap::ResultIteratorExt;
+    ///
+    /// let parsed: Result<Vec<u8>, _> = ["1", "x", "3"]
+    ///     .iter()
+    ///     .map(|s| s.parse::<u8>())
+    ///     .inspect_errs_or_log()
+    ///     .collect();
+    /// assert!(parsed.is_err());
+    /// ```
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn inspect_errs_or_log(self) -> InspectErrs<Self>;
+}
+
+impl<T, E: fmt::Debug, I: Iterator<Item = Result<T, E>>> ResultIteratorExt<T, E> for I {
+    #[inline]
+    #[track_caller]
+    fn inspect_errs_or_log(self) -> InspectErrs<Self> {
+        callsite::register("Iterator::inspect_errs_or_log");
+        InspectErrs {
+            inner: self,
+            index: 0,
+            location: Location::caller(),
+        }
+    }
+}
+
+/// An iterator that logs the errors passing through it, returned by
+/// [`ResultIteratorExt::inspect_errs_or_log`].
+#[derive(Clone, Debug)]
+pub struct InspectErrs<I> {
+    inner: I,
+    index: usize,
+    location: &'static Location<'static>,
+}
+
+impl<T, E: fmt::Debug, I: Iterator<Item = Result<T, E>>> Iterator for InspectErrs<I> {
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next()?;
+        match &item {
+            Ok(_) => recovery::succeeded("Iterator::inspect_errs_or_log", self.location),
+            Err(e) => inspected(self.index, e, self.location),
+        }
+        self.index += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T, E: fmt::Debug, I: FusedIterator<Item = Result<T, E>>> FusedIterator for InspectErrs<I> {}
+
+#[inline(never)]
+#[cold]
+fn inspected(index: usize, error: &dyn fmt::Debug, location: &'static Location<'static>) {
+    let msg = format!(
+        "item {} of `inspect_errs_or_log()` is an `Err` value",
+        index
+    );
+    let record = FailureRecord::new(
+        "Iterator::inspect_errs_or_log",
+        &msg,
+        Some(error),
+        crate::facade::Level::WARN,
+        false,
+    );
+    emit(&record.with_location(location));
+}
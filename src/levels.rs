@@ -0,0 +1,195 @@
+//! Severities that error types declare for themselves.
+
+use crate::facade::Level;
+use crate::{callsite, emit, fail, recovery, FailureRecord};
+use alloc::boxed::Box;
+use core::fmt;
+use core::panic::Location;
+#[cfg(feature = "std")]
+use std::io;
+
+/// An error type that declares the level its failures are logged at, so that
+/// the severity policy lives with the type rather than at every callsite.
+///
+/// ```
+/// use tracing::Level;
+/// use tracing_unwrap::LevelFor;
+///
+/// #[derive(Debug)]
+/// enum FetchError {
+///     CacheMiss,
+///     Corrupt,
+/// }
+///
+/// impl LevelFor for FetchError {
+///     fn level(&self) -> Level {
+///         match self {
+///             FetchError::CacheMiss => Level::DEBUG,
+///             FetchError::Corrupt => Level::ERROR,
+///         }
+///     }
+/// }
+/// ```
+///
+/// The level is used by the methods of [`LeveledResultExt`] and, with the
+/// `nightly` feature, by every `_or_log` method of
+/// [`ResultExt`](crate::ResultExt) and [`ErrorResultExt`](crate::ErrorResultExt).
+/// Those accept any error type, and only a nightly compiler can tell whether
+/// it implements this trait, through specialization.
+pub trait LevelFor {
+    /// The level a failure caused by this error is logged at.
+    fn level(&self) -> tracing::Level;
+}
+
+/// [`NotFound`](io::ErrorKind::NotFound) at a `WARN` level, anything else at
+/// an `ERROR` level.
+#[cfg(feature = "std")]
+impl LevelFor for io::ErrorKind {
+    fn level(&self) -> tracing::Level {
+        match self {
+            io::ErrorKind::NotFound => Level::WARN,
+            _ => Level::ERROR,
+        }
+    }
+}
+
+/// By the error's [`kind`](io::Error::kind).
+#[cfg(feature = "std")]
+impl LevelFor for io::Error {
+    fn level(&self) -> tracing::Level {
+        self.kind().level()
+    }
+}
+
+impl<E: LevelFor + ?Sized> LevelFor for &E {
+    fn level(&self) -> tracing::Level {
+        (**self).level()
+    }
+}
+
+impl<E: LevelFor + ?Sized> LevelFor for Box<E> {
+    fn level(&self) -> tracing::Level {
+        (**self).level()
+    }
+}
+
+/// Returns the level that `error` declares with [`LevelFor`], if its type
+/// implements it and the `nightly` feature is enabled.
+#[inline]
+pub(crate) fn declared<E: ?Sized>(error: &E) -> Option<Level> {
+    #[cfg(feature = "nightly")]
+    return MaybeLevelFor::declared(error);
+    #[cfg(not(feature = "nightly"))]
+    {
+        let _ = error;
+        None
+    }
+}
+
+/// Implemented by every type, to specialize on those that implement
+/// [`LevelFor`].
+#[cfg(feature = "nightly")]
+trait MaybeLevelFor {
+    fn declared(&self) -> Option<Level>;
+}
+
+#[cfg(feature = "nightly")]
+impl<E: ?Sized> MaybeLevelFor for E {
+    default fn declared(&self) -> Option<Level> {
+        None
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<E: LevelFor + ?Sized> MaybeLevelFor for E {
+    fn declared(&self) -> Option<Level> {
+        Some(self.level())
+    }
+}
+
+/// Extension trait for Result types whose error declares its own level with
+/// [`LevelFor`].
+pub trait LeveledResultExt<T, E> {
+    /// Converts `self` into an [`Option<T>`], consuming `self`, and logs the
+    /// error, if any, to a [`tracing::Subscriber`] at the level the error
+    /// declares.
+    fn ok_or_log_leveled(self) -> Option<T>;
+
+    /// Unwraps a result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging a message provided by the
+    /// [`Err`]'s value to a [`tracing::Subscriber`] at the level the error
+    /// declares.
+    fn unwrap_or_log_leveled(self) -> T;
+
+    /// Unwraps a result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging the passed message and the
+    /// content of the [`Err`] to a [`tracing::Subscriber`] at the level the
+    /// error declares.
+    fn expect_or_log_leveled(self, msg: &str) -> T;
+}
+
+impl<T, E: LevelFor + fmt::Debug> LeveledResultExt<T, E> for Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn ok_or_log_leveled(self) -> Option<T> {
+        callsite::register("Result::ok_or_log_leveled");
+        match self {
+            Ok(t) => {
+                recovery::succeeded("Result::ok_or_log_leveled", Location::caller());
+                Some(t)
+            }
+            Err(e) => {
+                discarded_leveled(
+                    "Result::ok_or_log_leveled",
+                    "called `Result::ok_or_log_leveled` on an `Err` value",
+                    &e,
+                );
+                None
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_leveled(self) -> T {
+        callsite::register("Result::unwrap_or_log_leveled");
+        match self {
+            Ok(t) => t,
+            Err(e) => failed_leveled(
+                "Result::unwrap_or_log_leveled",
+                "called `Result::unwrap_or_log_leveled()` on an `Err` value",
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_leveled(self, msg: &str) -> T {
+        callsite::register("Result::expect_or_log_leveled");
+        match self {
+            Ok(t) => t,
+            Err(e) => failed_leveled("Result::expect_or_log_leveled", msg, &e),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn failed_leveled<E: LevelFor + fmt::Debug>(method: &'static str, msg: &str, error: &E) -> ! {
+    fail(&FailureRecord::new(method, msg, Some(error), error.level(), true).with_caller_level())
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn discarded_leveled<E: LevelFor + fmt::Debug>(method: &'static str, msg: &str, error: &E) {
+    emit(&FailureRecord::new(method, msg, Some(error), error.level(), false).with_caller_level());
+}
@@ -24,27 +24,198 @@
 //! ```
 //!
 //! ### Methods
-//! | `std` method                               | `tracing-unwrap` form                 | trait         |
-//! | ------------------------------------------ | ------------------------------------- | ------------- |
-//! | [`Result::ok()`]                           | [`Result::ok_or_log()`]               | [`ResultExt`] |
-//! | [`Result::unwrap()`]                       | [`Result::unwrap_or_log()`]           | [`ResultExt`] |
-//! | [`Result::expect(msg)`]                    | [`Result::expect_or_log(msg)`]        | [`ResultExt`] |
-//! | [`Result::unwrap_err()`]                   | [`Result::unwrap_err_or_log()`]       | [`ResultExt`] |
-//! | [`Result::expect_err(msg)`]                | [`Result::expect_err_or_log(msg)`]    | [`ResultExt`] |
-//! | [`Option::unwrap()`]                       | [`Option::unwrap_or_log()`]           | [`OptionExt`] |
-//! | [`Option::expect(msg)`]                    | [`Option::expect_or_log(msg)`]        | [`OptionExt`] |
-//! | [`Option::unwrap_none()`]<sup>†</sup>      | [`Option::unwrap_none_or_log()`]      | [`OptionExt`] |
-//! | [`Option::expect_none(msg)`]<sup>†</sup>   | [`Option::expect_none_or_log(msg)`]   | [`OptionExt`] |
+//! | `std` method                                            | `tracing-unwrap` form                               | trait                 |
+//! | ------------------------------------------------------- | --------------------------------------------------- | --------------------- |
+//! | [`Result::ok()`]                                        | [`Result::ok_or_log()`]                             | [`ResultExt`]         |
+//! | [`Result::unwrap()`]                                    | [`Result::unwrap_or_log()`]                         | [`ResultExt`]         |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log(msg)`]                      | [`ResultExt`]         |
+//! | [`Result::unwrap_err()`]                                | [`Result::unwrap_err_or_log()`]                     | [`ResultExt`]         |
+//! | [`Result::expect_err(msg)`]                             | [`Result::expect_err_or_log(msg)`]                  | [`ResultExt`]         |
+//! | [`Option::unwrap()`]                                    | [`Option::unwrap_or_log()`]                         | [`OptionExt`]         |
+//! | [`Option::expect(msg)`]                                 | [`Option::expect_or_log(msg)`]                      | [`OptionExt`]         |
+//! | [`Option::unwrap_none()`]<sup>†</sup>                   | [`Option::unwrap_none_or_log()`]                    | [`OptionExt`]         |
+//! | [`Option::expect_none(msg)`]<sup>†</sup>                | [`Option::expect_none_or_log(msg)`]                 | [`OptionExt`]         |
+//! | [`Arc::try_unwrap(this)`]                               | [`Arc::try_unwrap_or_log()`]                        | [`ArcExt`]            |
+//! | [`Arc::into_inner(this)`]                               | [`Arc::into_inner_or_log()`]                        | [`ArcExt`]            |
+//! | [`Arc::get_mut(this)`]                                  | [`Arc::get_mut_or_log()`]                           | [`ArcExt`]            |
+//! | [`Rc::try_unwrap(this)`]                                | [`Rc::try_unwrap_or_log()`]                         | [`RcExt`]             |
+//! | [`Rc::into_inner(this)`]                                | [`Rc::into_inner_or_log()`]                         | [`RcExt`]             |
+//! | [`Rc::get_mut(this)`]                                   | [`Rc::get_mut_or_log()`]                            | [`RcExt`]             |
+//! | [`Box::<dyn Error>::downcast()`]                        | [`Box::<dyn Error>::downcast_or_log()`]             | [`BoxErrorExt`]       |
+//! | [`<dyn Error>::downcast_ref()`]                         | [`Box::<dyn Error>::downcast_ref_or_log()`]         | [`BoxErrorExt`]       |
+//! | [`str::split_once(delim)`]                              | [`str::split_once_or_log(delim)`]                   | [`StrExt`]            |
+//! | [`str::rsplit_once(delim)`]                             | [`str::rsplit_once_or_log(delim)`]                  | [`StrExt`]            |
+//! | [`str::strip_prefix(prefix)`]                           | [`str::strip_prefix_or_log(prefix)`]                | [`StrExt`]            |
+//! | [`str::strip_suffix(suffix)`]                           | [`str::strip_suffix_or_log(suffix)`]                | [`StrExt`]            |
+//! | [`char::to_digit(radix)`]                               | [`char::to_digit_or_log(radix)`]                    | [`CharExt`]           |
+//! | `c as u8`<sup>‡</sup>                                   | [`char::to_ascii_or_log()`]                         | [`CharExt`]           |
+//! | [`char::from_digit(num, radix)`]                        | [`u32::to_digit_char_or_log(radix)`]                | [`U32CharExt`]        |
+//! | [`char::from_u32(i)`]                                   | [`u32::to_char_or_log()`]                           | [`U32CharExt`]        |
+//! | [`Mutex::try_lock()`]`.unwrap()`                        | [`TryLockResult::lock_or_log()`]                    | [`TryLockResultExt`]  |
+//! | [`Mutex::try_lock()`]`.ok()`                            | [`TryLockResult::try_lock_or_log()`]                | [`TryLockResultExt`]  |
+//! | [`JoinHandle::join()`]`.unwrap()`                       | [`JoinHandle::join_or_log()`]                       | [`JoinHandleExt`]     |
+//! | [`Condvar::wait(guard)`]                                | [`Condvar::wait_or_log(guard)`]                     | [`CondvarExt`]        |
+//! | [`Condvar::wait_timeout(guard, dur)`]                   | [`Condvar::wait_timeout_or_log(guard, dur)`]        | [`CondvarExt`]        |
+//! | [`AtomicUsize::fetch_update(set, fetch, f)`]            | [`AtomicUsize::fetch_update_or_log(set, fetch, f)`] | [`AtomicExt`]         |
+//! | [`Result::ok()`]                                        | [`Result::ok_or_log_leveled()`]                     | [`LeveledResultExt`]  |
+//! | [`Result::unwrap()`]                                    | [`Result::unwrap_or_log_leveled()`]                 | [`LeveledResultExt`]  |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log_leveled(msg)`]              | [`LeveledResultExt`]  |
+//! | `reader.read_exact(buf).unwrap()`                       | [`LoggedReader::new(reader, label)`]                | —                     |
+//! | `writer.write_all(buf).unwrap()`                        | [`LoggedWriter::new(writer, label)`]                | —                     |
+//! | [`Iterator::inspect(f)`]                                | [`Iterator::inspect_errs_or_log()`]                 | [`ResultIteratorExt`] |
+//! | [`Option::transpose()`]`.unwrap()`                      | [`Option::transpose_or_log()`]                      | [`OptionResultExt`]   |
+//! | [`Child::wait()`]`.unwrap()`                            | [`Child::wait_or_log()`]                            | [`ChildExt`]          |
+//! | [`Child::kill()`]`.unwrap()`                            | [`Child::kill_or_log()`]                            | [`ChildExt`]          |
+//! | [`TcpStream::connect(addr)`]`.unwrap()`                 | [`TcpStream::connect_or_log(addr)`]                 | [`TcpStreamExt`]      |
+//! | [`TcpListener::bind(addr)`]`.unwrap()`                  | [`TcpListener::bind_or_log(addr)`]                  | [`TcpListenerExt`]    |
+//! | [`UdpSocket::bind(addr)`]`.unwrap()`                    | [`UdpSocket::bind_or_log(addr)`]                    | [`UdpSocketExt`]      |
+//! | [`Builder::spawn(f)`]`.unwrap()`                        | [`Builder::spawn_or_log(name, f)`]                  | [`BuilderExt`]        |
+//! | [`Builder::spawn_scoped(scope, f)`]`.unwrap()`          | [`Builder::spawn_scoped_or_log(scope, name, f)`]    | [`BuilderExt`]        |
+//! | [`thread::scope(f)`]                                    | [`scope_or_log(f)`]                                 | —                     |
+//! | [`Vec::try_reserve(n)`]`.unwrap()`                      | [`Vec::reserve_or_log(n)`]                          | [`TryReserveExt`]     |
+//! | [`Vec::try_reserve(n)`]`.is_ok()`                       | [`Vec::try_reserve_or_log(n)`]                      | [`TryReserveExt`]     |
+//! | [`Result::ok()`]                                        | [`Result::ok_or_log_at(level)`]                     | [`ResultExt`]         |
+//! | [`Result::unwrap()`]                                    | [`Result::unwrap_or_log_at(level)`]                 | [`ResultExt`]         |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log_at(level, msg)`]            | [`ResultExt`]         |
+//! | [`Option::unwrap()`]                                    | [`Option::unwrap_or_log_at(level)`]                 | [`OptionExt`]         |
+//! | [`Option::expect(msg)`]                                 | [`Option::expect_or_log_at(level, msg)`]            | [`OptionExt`]         |
+//! | [`Result::unwrap_or(default)`]                          | [`Result::unwrap_or_log_or(default)`]               | [`ResultExt`]         |
+//! | [`Result::unwrap_or_else(f)`]                           | [`Result::unwrap_or_else_or_log(f)`]                | [`ResultExt`]         |
+//! | [`Result::unwrap_or_default()`]                         | [`Result::unwrap_or_default_or_log()`]              | [`ResultExt`]         |
+//! | [`Option::unwrap_or(default)`]                          | [`Option::unwrap_or_log_or(default)`]               | [`OptionExt`]         |
+//! | [`Option::unwrap_or_else(f)`]                           | [`Option::unwrap_or_else_or_log(f)`]                | [`OptionExt`]         |
+//! | [`Option::unwrap_or_default()`]                         | [`Option::unwrap_or_default_or_log()`]              | [`OptionExt`]         |
+//! | [`Result::ok()`]                                        | [`Result::ok_or_log_error()`]                       | [`ErrorResultExt`]    |
+//! | [`Result::unwrap()`]                                    | [`Result::unwrap_or_log_error()`]                   | [`ErrorResultExt`]    |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log_error(msg)`]                | [`ErrorResultExt`]    |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log_with(f)`]                   | [`ResultExt`]         |
+//! | [`Result::expect_err(msg)`]                             | [`Result::expect_err_or_log_with(f)`]               | [`ResultExt`]         |
+//! | [`Option::expect(msg)`]                                 | [`Option::expect_or_log_with(f)`]                   | [`OptionExt`]         |
+//! | [`Option::expect_none(msg)`]<sup>†</sup>                | [`Option::expect_none_or_log_with(f)`]              | [`OptionExt`]         |
+//! | `fut.await.ok()`                                        | [`Future<Result>::ok_or_log()`]                     | [`ResultFutureExt`]   |
+//! | `fut.await.unwrap()`                                    | [`Future<Result>::unwrap_or_log()`]                 | [`ResultFutureExt`]   |
+//! | `fut.await.expect(msg)`                                 | [`Future<Result>::expect_or_log(msg)`]              | [`ResultFutureExt`]   |
+//! | `fut.await.unwrap()`                                    | [`Future<Option>::unwrap_or_log()`]                 | [`OptionFutureExt`]   |
+//! | `fut.await.expect(msg)`                                 | [`Future<Option>::expect_or_log(msg)`]              | [`OptionFutureExt`]   |
+//! | [`Result::ok()`]                                        | [`Result::ok_or_log_opaque()`]                      | [`ResultExt`]         |
+//! | [`Result::unwrap()`]                                    | [`Result::unwrap_or_log_opaque()`]                  | [`ResultExt`]         |
+//! | [`Result::expect(msg)`]                                 | [`Result::expect_or_log_redacted(msg)`]             | [`ResultExt`]         |
+//! | `let ControlFlow::Continue(c) = flow else { panic!() }` | [`ControlFlow::continue_or_log()`]                  | [`ControlFlowExt`]    |
+//! | `let ControlFlow::Break(b) = flow else { panic!() }`    | [`ControlFlow::break_or_log()`]                     | [`ControlFlowExt`]    |
+//! | `poll.map(Result::unwrap)`                              | [`Poll::unwrap_ready_or_log()`]                     | [`PollExt`]           |
+//! | `poll`                                                  | [`Poll::inspect_err_or_log()`]                      | [`PollExt`]           |
+//! | [`slice::get(index)`]`.unwrap()`                        | [`slice::get_or_log(index)`]                        | [`LookupExt`]         |
+//! | `slice.get_mut(index).unwrap()`                         | [`slice::get_mut_or_log(index)`]                    | [`LookupExt`]         |
+//! | [`HashMap::get(key)`]`.unwrap()`                        | [`HashMap::get_or_log(key)`]                        | [`LookupExt`]         |
 //!
 //! *†: no longer in `std`, see [`rust-lang/rust#62633`](https://github.com/rust-lang/rust/issues/62633)*<br/>
+//! *‡: a plain cast, with the character checked to be ASCII first*<br/>
 //!
+//! An error whose type declares its own level with [`LevelFor`] is logged at that level by the `_leveled` methods of [`LeveledResultExt`]. With the **`nightly`** feature, every `_or_log` method of [`ResultExt`] and [`ErrorResultExt`] consults [`LevelFor`] too, when the error implements it; on a stable compiler they log at fixed levels, as stable Rust offers no way to check whether an error type implements a trait.
+//!
+//! ### Macros
+//! Checks that don't fit an extension trait come as macros, which also log the source text of what failed, as do macro forms of the plain unwraps:
+//!
+//! | `std` form                                        | `tracing-unwrap` form                          |
+//! | ------------------------------------------------- | ---------------------------------------------- |
+//! | `assert!(matches!(expr, pattern))`                | [`matches_or_log!(expr, pattern)`]             |
+//! | `let Ok(pattern) = expr else { panic!() }`        | [`let_ok_or_log!(let Ok(pattern) = expr)`]     |
+//! | `let Some(pattern) = expr else { panic!() }`      | [`let_some_or_log!(let Some(pattern) = expr)`] |
+//! | `if !cond { return value; }`                      | [`guard_or_log!(cond, value)`]                 |
+//! | `(a.unwrap(), b.unwrap(), ...)`                   | [`unwrap_all_or_log!(a, b, ...)`]              |
+//! | `tokio::select! { v = fut_a => v.unwrap(), ... }` | [`select_or_log! { a = fut_a, ... }`]          |
+//! | `tokio::select! { v = fut_a => v, ... }`          | [`try_select_or_log! { a = fut_a, ... }`]      |
+//! | `{ ... }`                                         | [`logged_scope!(name, { ... })`]               |
+//! | `expr.unwrap()`                                   | [`unwrap_or_log!(expr)`]                       |
+//! | `expr.expect(msg)`                                | [`expect_or_log!(expr, msg)`]                  |
 //!
 //! ### Features
 //! * **`panic-quiet`**: causes failed unwraps to panic with an empty message.<br/>
 //!   This feature is enabled by default — if you'd like the unwrap error message to also show in the panic message, disable default features in your `Cargo.toml` as follows:<br/>
-//!   `tracing-unwrap = { version = "1.0", default-features = false }`
+//!   `tracing-unwrap = { version = "1.0", default-features = false, features = ["std"] }`
+//!
+//! * **`std`**: uses the standard library, and is enabled by default. Without it, the crate is `no_std` and only needs `alloc`: the extension methods, the macros other than [`logged_scope!`] and the `nb` and `heapless` extensions remain, logging through the default dispatcher, while everything else that is configured at runtime or touches threads or I/O is left out. [`set_clock()`] remains too, and can be called once to supply the tick source of the target, as there is no default clock. Every other feature but `panic-quiet`, `log-location`, `otel-fields`, `structured-errors`, `nb`, `heapless` and `derive` enables it.
+//!
+//! * **`log-location`**: calls [`core::panic::Location::caller()`] to determine the location of a failed unwrap.
+//!
+//! * **`nb`**: adds [`NbResultExt`] and [`block_or_log()`] for the `nb::Result` values returned by `embedded-hal` drivers, logging `WouldBlock` and driver errors distinctly.
+//!
+//! * **`heapless`**: adds [`HeaplessVecExt`] and [`HeaplessIndexMapExt`] for `heapless` collections, logging the capacity and the attempted length when an insertion doesn't fit.
+//!
+//! * **`test-util`**: adds [`test_util::Replay`], a guard that buffers failure events during a test and prints them, with their location and fields, after the panic message if the test panics.
+//!
+//! * **`tokio`**: adds [`timeout_or_log()`] and [`try_timeout_or_log()`], which bound a future by a timeout and log the operation label, the configured duration and the elapsed time when it expires. Also adds [`retry_or_log()`], which retries an async operation with a [`Backoff`] policy, logging each failed attempt at `WARN` and only the last one at `ERROR`. Also adds [`BroadcastReceiverExt`], [`WatchReceiverExt`] and [`MpscSenderExt`] for tokio's channels, which log how many messages a lagging `broadcast` receiver skipped, and tell a full `mpsc` channel from a closed one.
+//!
+//! * **`callsite-inventory`**: adds [`callsites()`], which lists every place that has called one of this crate's methods or macros so far, with the method used — a starting point for auditing where a program is allowed to die. Callsites register themselves the first time they run, so only code that has been exercised is listed. It also counts the failures of each callsite, recording the count as the `unwrap.occurrence` field, so that a single line tells whether a failure is the first or the ten-thousandth at its callsite — as does installing an escalation policy.
+//!
+//! * **`panic-payload`**: makes failed unwraps panic with an [`UnwrapFailure`] payload carrying the message, the rendered value, the level and the location, so that custom panic hooks and supervisors can downcast it instead of parsing the panic message. It takes precedence over **`panic-quiet`**, while a panic formatter, if one is set, takes precedence over it.
+//!
+//! * **`backtrace`**: records a backtrace of each failure as the `unwrap.stacktrace` field. Like the standard library's own backtraces, it is trimmed of the frames of the backtrace machinery and of this crate, so that it starts at the failed call.
+//!
+//! * **`build-metadata`**: adds [`set_build_metadata()`], which registers the git commit, profile and timestamp of the running build once at startup, to be stamped onto every failure event as `unwrap.build.*` fields so that crash logs are attributable to the exact build.
+//!
+//! * **`termination-log`**: makes a fatal failure write a short summary — the message, the location and the callsite identifier — to `/dev/termination-log` before panicking, so that `kubectl describe pod` shows why a container died even if its logs were lost. [`set_termination_log_path()`] changes the file, to match the container's `terminationMessagePath`.
+//!
+//! * **`otel`**: records the baggage of the current OpenTelemetry context, such as a tenant or a request id propagated across services, as the `unwrap.baggage` field of failure events, in the W3C `baggage` header format — so that failures correlate across services without manual plumbing.
+//!
+//! * **`attributes`**: adds the [`unwrap_context`] attribute, e.g. `#[unwrap_context("loading user", user_id)]`, which attaches a context string, optionally followed by selected arguments, to every failure inside a function as the `unwrap.context` field — without converting every callsite to `expect_or_log`. Contexts nest, and apply to `async fn`s whenever their future is polled.
+//!
+//! * **`parking_lot`**: adds [`ParkingLotMutexExt`] and [`ParkingLotRwLockExt`], with `try_lock_or_log(label)`, `try_lock_for_or_log(label, timeout)` and their read and write counterparts for `parking_lot` locks, which can't be poisoned and so aren't covered by [`TryLockResultExt`]. A failed attempt is logged at `WARN` with the lock's label, how long it waited and what held the lock.
+//!
+//! * **`nightly`**: requires a nightly compiler, and records the backtrace that an error provides itself through the `Error::provide` API, e.g. one captured when it was created, as the `unwrap.stacktrace` field — so that errors which already carry a backtrace don't need a second capture, even with the **`backtrace`** feature. It also makes the methods of [`ResultExt`] and [`ErrorResultExt`] log an error at the level its type declares with [`LevelFor`].
+//!
+//! * **`derive`**: adds `#[derive(LevelFor)]`, which implements [`LevelFor`] from `#[level(warn)]`-style attributes on the variants of an error enum and on the type itself, so that the severity policy of a large error type is declared next to its definition.
+//!
+//! * **`structured-errors`**: also records the value of a failure as an `error` field, rather than only interpolating it into the message, so that log pipelines can query on it. Errors passed to the methods of [`ErrorResultExt`] are recorded through `tracing`'s support for error values, which captures their whole source chain; other values are recorded with their `Debug` representation. Without the **`std`** feature, errors are recorded with their `Debug` representation too, as `tracing` only supports error values with the standard library; their sources are still logged as `unwrap.sources`.
+//!
+//! * **`otel-fields`**: names the fields of failure events after OpenTelemetry's semantic conventions, so that OTel backends ingest them without mapping rules: the location fields of **`log-location`** become `code.filepath`, `code.lineno` and `code.column`, and every event records the rendered failure as `exception.message` and, for the methods of [`ResultExt`], the type name of the error as `exception.type`.
+//!
+//! * **`panic-hook`**: adds [`init_panic_hook()`], which installs a panic hook that logs every panic — from `unwrap()`, indexing, `panic!` or a dependency — as an `ERROR` event with the thread name, the location and, with the **`backtrace`** feature, a stack trace, so that panics reach the same log pipeline as failed unwraps. Failures already logged by this crate are not logged again. [`init_panic_hook_chained()`] keeps the previous hook, e.g. to still print to stderr.
+//!
+//! ### Configuration
+//! Behavior that is chosen at runtime is configured through process-wide setters:
+//!
+//! * [`set_filter()`] installs a [`Filter`] that decides, by the caller's source file path, which failed unwraps are emitted and at which levels — independently of the subscriber's own filter, which only sees this crate as the origin of every event.
+//!
+//! * [`set_event_formatter()`] and [`set_panic_formatter()`] register a function that turns a [`FailureRecord`] into the final event or panic message, for enforcing a house style across all failures. The provided [`json_formatter()`] renders the panic message as a single-line JSON object, so that platforms that only capture the panic line still get machine-parseable crash data.
+//!
+//! * [`set_escalation()`] installs an [`Escalation`] policy that picks the level of a failure from how often its callsite has failed, e.g. logging the first few at `WARN` and any further ones at `ERROR`.
+//!
+//! * [`set_shutdown_hook()`] and [`set_async_shutdown_hook()`] register a hook that the first fatal failure runs, with a timeout, before panicking — e.g. to drain in-flight requests or flush exporters.
+//!
+//! * [`set_dispatch()`] sends every failure event to an explicitly supplied `tracing::Dispatch` rather than the current default one, for plugins and libraries that maintain their own isolated subscriber.
+//!
+//! * [`install_quiet_panic_hook()`] installs a panic hook that shortens the stderr output of failed unwraps to a single line, since they were already logged, and defers to the previous hook for every other panic.
+//!
+//! * [`set_max_source_depth()`] limits how many sources of an error are logged in the `unwrap.sources` field, marking longer chains with `unwrap.sources_truncated`, so that a pathological or cyclic chain can't produce unbounded output.
+//!
+//! * [`set_reporter()`] replaces the [`FailureReporter`] that every failure goes through — by default one that emits the tracing event — to forward failures to an error-reporting hook, a crash server or a queue as well, making this crate the single funnel for fatal-error reporting.
+//!
+//! * [`set_circuit_breaker()`] installs a [`CircuitBreaker`] that, once too many failures happen within a time window, globally or at one callsite, makes fatal failures abort instead of panicking, or discarded errors panic instead of only being logged — to stop crash loops from flapping silently.
+//!
+//! * [`set_clock()`] replaces the [`Clock`] that time windows are measured with, so that tests can drive time deterministically and embedded targets can supply their own tick source.
 //!
-//! * **`log-location`**: calls [`std::panic::Location::caller()`] to determine the location of a failed unwrap.
+//! * [`set_instance_id()`] attaches an identifier of the process instance, such as a pod name, to every failure event as the `unwrap.instance_id` field, for fleets that send their logs into a shared store without per-host enrichment. With the **`hostname`** feature, [`set_instance_id_from_hostname()`] uses the machine's hostname.
+//!
+//! * [`set_recovery_events()`] makes the first success of a non-panicking method, at a callsite that logged failures since its last success, emit an `INFO` event reading "recovered after N failures over D", so that dashboards show both an incident and its resolution.
+//!
+//! * [`set_sampling_priority()`] makes fatal failures record a `sampling.priority` field, on their event and on the current span if it declares the field, so that tail-sampling collectors can always retain traces that contain a fatal unwrap.
+//!
+//! * [`set_debug_defaults()`] and [`set_release_defaults()`] install [`ProfileDefaults`] — a level for failures whose method defaults to `ERROR`, and whether values are redacted — for builds with and without debug assertions respectively, so that "chatty in dev, conservative in prod" is encoded once rather than per deployment.
+//!
+//! * [`set_async_span_capture()`] chooses whether the failure events of async adapters are emitted in the span that is current when their future is polled, as by default, or in the one that was current when it was created — since executors often poll in an unrelated span, and the event would lose its request context. The `_in_current_span` forms of the adapters of [`ResultFutureExt`] and [`OptionFutureExt`] make that choice for a single future, whatever the executor.
+//!
+//! * A [`LogBuffer`] collects the failure events of non-panicking methods on the current thread during a unit of work, to emit them together if it fails, or discard them if it succeeds — e.g. for retried transactions, whose intermediate failures only matter if the whole operation fails.
+//!
+//! * [`set_enabled()`] is a kill switch that suppresses every failure event at runtime — while failures still panic as usual — so that operators can silence a log storm from a known issue without redeploying.
+//!
+//! * The `TRACING_UNWRAP_TARGET` environment variable, when set at build time, replaces `tracing_unwrap` as the target of every event of this crate, e.g. `TRACING_UNWRAP_TARGET=billing::unwrap`, so that failures can be filtered and routed with `EnvFilter` directives like the events of the program itself. `tracing` stores targets in the static metadata of each callsite, so the target can't be chosen per call.
+//!
+//! * [`set_failure_behavior()`] picks a [`FailureBehavior`] for fatal failures once their event is emitted: panicking, as by default, aborting the process, e.g. to get a core dump, or exiting with a given code. For a typed panic payload that a panic hook recognizes, use the **`panic-payload`** feature.
+//!
+//! * [`set_failure_hook()`] registers a function that every failure runs, after its event and before any panic — including failures that are filtered out — to increment a metrics counter or leave a breadcrumb for an error tracker without wrapping each call.
+//!
+//! * [`set_rate_limit()`] installs a [`RateLimit`] that logs at most a few discarded errors per callsite within a time window, so that an `ok_or_log()` in a hot loop can't flood the logs during an outage. Once a window closes, a summary event records how many were suppressed as the `suppressed` entry of the `unwrap.details` field, even if the callsite has stopped failing.
 //!
 //! [`tracing::Subscriber`]: https://docs.rs/tracing/*/tracing/trait.Subscriber.html
 //! [`ResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html
@@ -68,15 +239,466 @@
 //! [`Option::expect_or_log(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.expect_or_log
 //! [`Option::unwrap_none_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.unwrap_none_or_log
 //! [`Option::expect_none_or_log(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.expect_none_or_log
-//! [`std::panic::Location::caller()`]: https://doc.rust-lang.org/std/panic/struct.Location.html#method.caller
+//! [`core::panic::Location::caller()`]: https://doc.rust-lang.org/std/panic/struct.Location.html#method.caller
+//! [`NbResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.NbResultExt.html
+//! [`block_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.block_or_log.html
+//! [`HeaplessVecExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.HeaplessVecExt.html
+//! [`HeaplessIndexMapExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.HeaplessIndexMapExt.html
+//! [`ArcExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ArcExt.html
+//! [`RcExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.RcExt.html
+//! [`Arc::try_unwrap(this)`]: https://doc.rust-lang.org/std/sync/struct.Arc.html#method.try_unwrap
+//! [`Arc::into_inner(this)`]: https://doc.rust-lang.org/std/sync/struct.Arc.html#method.into_inner
+//! [`Arc::get_mut(this)`]: https://doc.rust-lang.org/std/sync/struct.Arc.html#method.get_mut
+//! [`Rc::try_unwrap(this)`]: https://doc.rust-lang.org/std/rc/struct.Rc.html#method.try_unwrap
+//! [`Rc::into_inner(this)`]: https://doc.rust-lang.org/std/rc/struct.Rc.html#method.into_inner
+//! [`Rc::get_mut(this)`]: https://doc.rust-lang.org/std/rc/struct.Rc.html#method.get_mut
+//! [`Arc::try_unwrap_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ArcExt.html#tymethod.try_unwrap_or_log
+//! [`Arc::into_inner_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ArcExt.html#tymethod.into_inner_or_log
+//! [`Arc::get_mut_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ArcExt.html#tymethod.get_mut_or_log
+//! [`Rc::try_unwrap_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.RcExt.html#tymethod.try_unwrap_or_log
+//! [`Rc::into_inner_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.RcExt.html#tymethod.into_inner_or_log
+//! [`Rc::get_mut_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.RcExt.html#tymethod.get_mut_or_log
+//! [`BoxErrorExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BoxErrorExt.html
+//! [`Box::<dyn Error>::downcast()`]: https://doc.rust-lang.org/std/boxed/struct.Box.html#method.downcast-2
+//! [`<dyn Error>::downcast_ref()`]: https://doc.rust-lang.org/std/error/trait.Error.html#method.downcast_ref
+//! [`Box::<dyn Error>::downcast_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BoxErrorExt.html#tymethod.downcast_or_log
+//! [`Box::<dyn Error>::downcast_ref_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BoxErrorExt.html#tymethod.downcast_ref_or_log
+//! [`StrExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.StrExt.html
+//! [`str::split_once(delim)`]: https://doc.rust-lang.org/std/primitive.str.html#method.split_once
+//! [`str::rsplit_once(delim)`]: https://doc.rust-lang.org/std/primitive.str.html#method.rsplit_once
+//! [`str::strip_prefix(prefix)`]: https://doc.rust-lang.org/std/primitive.str.html#method.strip_prefix
+//! [`str::strip_suffix(suffix)`]: https://doc.rust-lang.org/std/primitive.str.html#method.strip_suffix
+//! [`str::split_once_or_log(delim)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.StrExt.html#tymethod.split_once_or_log
+//! [`str::rsplit_once_or_log(delim)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.StrExt.html#tymethod.rsplit_once_or_log
+//! [`str::strip_prefix_or_log(prefix)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.StrExt.html#tymethod.strip_prefix_or_log
+//! [`str::strip_suffix_or_log(suffix)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.StrExt.html#tymethod.strip_suffix_or_log
+//! [`CharExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CharExt.html
+//! [`U32CharExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.U32CharExt.html
+//! [`char::to_digit(radix)`]: https://doc.rust-lang.org/std/primitive.char.html#method.to_digit
+//! [`char::from_digit(num, radix)`]: https://doc.rust-lang.org/std/primitive.char.html#method.from_digit
+//! [`char::from_u32(i)`]: https://doc.rust-lang.org/std/primitive.char.html#method.from_u32
+//! [`char::to_digit_or_log(radix)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CharExt.html#tymethod.to_digit_or_log
+//! [`char::to_ascii_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CharExt.html#tymethod.to_ascii_or_log
+//! [`u32::to_digit_char_or_log(radix)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.U32CharExt.html#tymethod.to_digit_char_or_log
+//! [`u32::to_char_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.U32CharExt.html#tymethod.to_char_or_log
+//! [`set_filter()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_filter.html
+//! [`Filter`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.Filter.html
+//! [`set_event_formatter()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_event_formatter.html
+//! [`set_panic_formatter()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_panic_formatter.html
+//! [`FailureRecord`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.FailureRecord.html
+//! [`set_escalation()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_escalation.html
+//! [`Escalation`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.Escalation.html
+//! [`set_shutdown_hook()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_shutdown_hook.html
+//! [`set_async_shutdown_hook()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_async_shutdown_hook.html
+//! [`test_util::Replay`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/test_util/struct.Replay.html
+//! [`matches_or_log!(expr, pattern)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.matches_or_log.html
+//! [`let_ok_or_log!(let Ok(pattern) = expr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.let_ok_or_log.html
+//! [`let_some_or_log!(let Some(pattern) = expr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.let_some_or_log.html
+//! [`guard_or_log!(cond, value)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.guard_or_log.html
+//! [`unwrap_all_or_log!(a, b, ...)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.unwrap_all_or_log.html
+//! [`select_or_log! { a = fut_a, ... }`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.select_or_log.html
+//! [`try_select_or_log! { a = fut_a, ... }`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.try_select_or_log.html
+//! [`timeout_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.timeout_or_log.html
+//! [`retry_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.retry_or_log.html
+//! [`Backoff`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.Backoff.html
+//! [`try_timeout_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.try_timeout_or_log.html
+//! [`callsites()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.callsites.html
+//! [`set_dispatch()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_dispatch.html
+//! [`json_formatter()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.json_formatter.html
+//! [`UnwrapFailure`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.UnwrapFailure.html
+//! [`install_quiet_panic_hook()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.install_quiet_panic_hook.html
+//! [`set_max_source_depth()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_max_source_depth.html
+//! [`set_reporter()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_reporter.html
+//! [`FailureReporter`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.FailureReporter.html
+//! [`Mutex::try_lock()`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html#method.try_lock
+//! [`TryLockResult::lock_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryLockResultExt.html#tymethod.lock_or_log
+//! [`TryLockResult::try_lock_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryLockResultExt.html#tymethod.try_lock_or_log
+//! [`TryLockResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryLockResultExt.html
+//! [`JoinHandle::join()`]: https://doc.rust-lang.org/std/thread/struct.JoinHandle.html#method.join
+//! [`JoinHandle::join_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.JoinHandleExt.html#tymethod.join_or_log
+//! [`JoinHandleExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.JoinHandleExt.html
+//! [`Condvar::wait(guard)`]: https://doc.rust-lang.org/std/sync/struct.Condvar.html#method.wait
+//! [`Condvar::wait_timeout(guard, dur)`]: https://doc.rust-lang.org/std/sync/struct.Condvar.html#method.wait_timeout
+//! [`Condvar::wait_or_log(guard)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CondvarExt.html#tymethod.wait_or_log
+//! [`Condvar::wait_timeout_or_log(guard, dur)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CondvarExt.html#tymethod.wait_timeout_or_log
+//! [`CondvarExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.CondvarExt.html
+//! [`AtomicUsize::fetch_update(set, fetch, f)`]: https://doc.rust-lang.org/std/sync/atomic/struct.AtomicUsize.html#method.fetch_update
+//! [`AtomicUsize::fetch_update_or_log(set, fetch, f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.AtomicExt.html#tymethod.fetch_update_or_log
+//! [`AtomicExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.AtomicExt.html
+//! [`Result::ok_or_log_leveled()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LeveledResultExt.html#tymethod.ok_or_log_leveled
+//! [`Result::unwrap_or_log_leveled()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LeveledResultExt.html#tymethod.unwrap_or_log_leveled
+//! [`Result::expect_or_log_leveled(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LeveledResultExt.html#tymethod.expect_or_log_leveled
+//! [`LeveledResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LeveledResultExt.html
+//! [`set_circuit_breaker()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_circuit_breaker.html
+//! [`CircuitBreaker`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.CircuitBreaker.html
+//! [`set_clock()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_clock.html
+//! [`Clock`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.Clock.html
+//! [`LoggedReader::new(reader, label)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.LoggedReader.html
+//! [`LoggedWriter::new(writer, label)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.LoggedWriter.html
+//! [`Iterator::inspect(f)`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.inspect
+//! [`Iterator::inspect_errs_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultIteratorExt.html#tymethod.inspect_errs_or_log
+//! [`ResultIteratorExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultIteratorExt.html
+//! [`Option::transpose()`]: https://doc.rust-lang.org/std/option/enum.Option.html#method.transpose
+//! [`Option::transpose_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionResultExt.html#tymethod.transpose_or_log
+//! [`OptionResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionResultExt.html
+//! [`Child::wait()`]: https://doc.rust-lang.org/std/process/struct.Child.html#method.wait
+//! [`Child::kill()`]: https://doc.rust-lang.org/std/process/struct.Child.html#method.kill
+//! [`Child::wait_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ChildExt.html#tymethod.wait_or_log
+//! [`Child::kill_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ChildExt.html#tymethod.kill_or_log
+//! [`ChildExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ChildExt.html
+//! [`TcpStream::connect(addr)`]: https://doc.rust-lang.org/std/net/struct.TcpStream.html#method.connect
+//! [`TcpListener::bind(addr)`]: https://doc.rust-lang.org/std/net/struct.TcpListener.html#method.bind
+//! [`UdpSocket::bind(addr)`]: https://doc.rust-lang.org/std/net/struct.UdpSocket.html#method.bind
+//! [`TcpStream::connect_or_log(addr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TcpStreamExt.html#tymethod.connect_or_log
+//! [`TcpListener::bind_or_log(addr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TcpListenerExt.html#tymethod.bind_or_log
+//! [`UdpSocket::bind_or_log(addr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.UdpSocketExt.html#tymethod.bind_or_log
+//! [`TcpStreamExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TcpStreamExt.html
+//! [`TcpListenerExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TcpListenerExt.html
+//! [`UdpSocketExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.UdpSocketExt.html
+//! [`set_build_metadata()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_build_metadata.html
+//! [`set_instance_id()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_instance_id.html
+//! [`set_instance_id_from_hostname()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_instance_id_from_hostname.html
+//! [`set_recovery_events()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_recovery_events.html
+//! [`set_termination_log_path()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_termination_log_path.html
+//! [`set_sampling_priority()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_sampling_priority.html
+//! [`unwrap_context`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/attr.unwrap_context.html
+//! [`logged_scope!(name, { ... })`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.logged_scope.html
+//! [`logged_scope!`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.logged_scope.html
+//! [`set_debug_defaults()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_debug_defaults.html
+//! [`set_release_defaults()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_release_defaults.html
+//! [`ProfileDefaults`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.ProfileDefaults.html
+//! [`ParkingLotMutexExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ParkingLotMutexExt.html
+//! [`ParkingLotRwLockExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ParkingLotRwLockExt.html
+//! [`BroadcastReceiverExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BroadcastReceiverExt.html
+//! [`WatchReceiverExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.WatchReceiverExt.html
+//! [`MpscSenderExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.MpscSenderExt.html
+//! [`set_async_span_capture()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_async_span_capture.html
+//! [`LogBuffer`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.LogBuffer.html
+//! [`set_enabled()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_enabled.html
+//! [`LevelFor`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LevelFor.html
+//! [`Builder::spawn(f)`]: https://doc.rust-lang.org/std/thread/struct.Builder.html#method.spawn
+//! [`Builder::spawn_or_log(name, f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BuilderExt.html#tymethod.spawn_or_log
+//! [`Builder::spawn_scoped(scope, f)`]: https://doc.rust-lang.org/std/thread/struct.Builder.html#method.spawn_scoped
+//! [`Builder::spawn_scoped_or_log(scope, name, f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BuilderExt.html#tymethod.spawn_scoped_or_log
+//! [`BuilderExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.BuilderExt.html
+//! [`thread::scope(f)`]: https://doc.rust-lang.org/std/thread/fn.scope.html
+//! [`scope_or_log(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.scope_or_log.html
+//! [`Vec::try_reserve(n)`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.try_reserve
+//! [`Vec::reserve_or_log(n)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryReserveExt.html#tymethod.reserve_or_log
+//! [`Vec::try_reserve_or_log(n)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryReserveExt.html#tymethod.try_reserve_or_log
+//! [`TryReserveExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.TryReserveExt.html
+//! [`Result::ok_or_log_at(level)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.ok_or_log_at
+//! [`Result::unwrap_or_log_at(level)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.unwrap_or_log_at
+//! [`Result::expect_or_log_at(level, msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.expect_or_log_at
+//! [`Option::unwrap_or_log_at(level)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.unwrap_or_log_at
+//! [`Option::expect_or_log_at(level, msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.expect_or_log_at
+//! [`Result::unwrap_or(default)`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap_or
+//! [`Result::unwrap_or_else(f)`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap_or_else
+//! [`Result::unwrap_or_default()`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap_or_default
+//! [`Option::unwrap_or(default)`]: https://doc.rust-lang.org/std/option/enum.Option.html#method.unwrap_or
+//! [`Option::unwrap_or_else(f)`]: https://doc.rust-lang.org/std/option/enum.Option.html#method.unwrap_or_else
+//! [`Option::unwrap_or_default()`]: https://doc.rust-lang.org/std/option/enum.Option.html#method.unwrap_or_default
+//! [`Result::unwrap_or_log_or(default)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.unwrap_or_log_or
+//! [`Result::unwrap_or_else_or_log(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.unwrap_or_else_or_log
+//! [`Result::unwrap_or_default_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.unwrap_or_default_or_log
+//! [`Option::unwrap_or_log_or(default)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.unwrap_or_log_or
+//! [`Option::unwrap_or_else_or_log(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.unwrap_or_else_or_log
+//! [`Option::unwrap_or_default_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.unwrap_or_default_or_log
+//! [`Result::ok_or_log_error()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ErrorResultExt.html#tymethod.ok_or_log_error
+//! [`Result::unwrap_or_log_error()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ErrorResultExt.html#tymethod.unwrap_or_log_error
+//! [`Result::expect_or_log_error(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ErrorResultExt.html#tymethod.expect_or_log_error
+//! [`ErrorResultExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ErrorResultExt.html
+//! [`Result::expect_or_log_with(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.expect_or_log_with
+//! [`Result::expect_err_or_log_with(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.expect_err_or_log_with
+//! [`Option::expect_or_log_with(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.expect_or_log_with
+//! [`Option::expect_none_or_log_with(f)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionExt.html#tymethod.expect_none_or_log_with
+//! [`Future<Result>::ok_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultFutureExt.html#tymethod.ok_or_log
+//! [`Future<Result>::unwrap_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultFutureExt.html#tymethod.unwrap_or_log
+//! [`Future<Result>::expect_or_log(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultFutureExt.html#tymethod.expect_or_log
+//! [`Future<Option>::unwrap_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionFutureExt.html#tymethod.unwrap_or_log
+//! [`Future<Option>::expect_or_log(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionFutureExt.html#tymethod.expect_or_log
+//! [`ResultFutureExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultFutureExt.html
+//! [`OptionFutureExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.OptionFutureExt.html
+//! [`unwrap_or_log!(expr)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.unwrap_or_log.html
+//! [`expect_or_log!(expr, msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/macro.expect_or_log.html
+//! [`set_failure_behavior()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_failure_behavior.html
+//! [`FailureBehavior`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/enum.FailureBehavior.html
+//! [`set_failure_hook()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_failure_hook.html
+//! [`Result::ok_or_log_opaque()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.ok_or_log_opaque
+//! [`Result::unwrap_or_log_opaque()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.unwrap_or_log_opaque
+//! [`Result::expect_or_log_redacted(msg)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ResultExt.html#tymethod.expect_or_log_redacted
+//! [`ControlFlow::continue_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ControlFlowExt.html#tymethod.continue_or_log
+//! [`ControlFlow::break_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ControlFlowExt.html#tymethod.break_or_log
+//! [`Poll::unwrap_ready_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.PollExt.html#tymethod.unwrap_ready_or_log
+//! [`Poll::inspect_err_or_log()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.PollExt.html#tymethod.inspect_err_or_log
+//! [`ControlFlowExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.ControlFlowExt.html
+//! [`PollExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.PollExt.html
+//! [`slice::get(index)`]: https://doc.rust-lang.org/std/primitive.slice.html#method.get
+//! [`HashMap::get(key)`]: https://doc.rust-lang.org/std/collections/struct.HashMap.html#method.get
+//! [`slice::get_or_log(index)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LookupExt.html#tymethod.get_or_log
+//! [`slice::get_mut_or_log(index)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LookupExt.html#tymethod.get_mut_or_log
+//! [`HashMap::get_or_log(key)`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LookupExt.html#tymethod.get_or_log
+//! [`LookupExt`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/trait.LookupExt.html
+//! [`set_rate_limit()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.set_rate_limit.html
+//! [`RateLimit`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/struct.RateLimit.html
+//! [`init_panic_hook()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.init_panic_hook.html
+//! [`init_panic_hook_chained()`]: https://docs.rs/tracing-unwrap/*/tracing_unwrap/fn.init_panic_hook_chained.html
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(error_generic_member_access))]
+#![cfg_attr(feature = "nightly", feature(specialization))]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+mod atomics;
+pub use atomics::AtomicExt;
+
+#[cfg(feature = "tokio")]
+mod channels;
+#[cfg(feature = "tokio")]
+pub use channels::{BroadcastReceiverExt, MpscSenderExt, WatchReceiverExt};
+
+mod chars;
+pub use chars::{CharExt, U32CharExt};
+
+#[cfg(feature = "std")]
+mod behavior;
+#[cfg(feature = "std")]
+pub use behavior::{set_failure_behavior, FailureBehavior};
+
+#[cfg(feature = "std")]
+mod breaker;
+#[cfg(feature = "std")]
+pub use breaker::{set_circuit_breaker, CircuitBreaker};
+
+#[cfg(feature = "build-metadata")]
+mod build_info;
+#[cfg(feature = "build-metadata")]
+pub use build_info::{set_build_metadata, BuildMetadata};
+
+#[cfg(feature = "std")]
+mod buffer;
+#[cfg(feature = "std")]
+pub use buffer::LogBuffer;
+
+mod callsite;
+#[cfg(feature = "callsite-inventory")]
+pub use callsite::{callsites, Callsite};
+
+#[cfg(feature = "std")]
+mod context;
+#[cfg(feature = "attributes")]
+pub use tracing_unwrap_macros::unwrap_context;
+
+mod dispatch;
+#[cfg(feature = "std")]
+pub use dispatch::set_dispatch;
+
+mod clock;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{set_clock, Clock};
+
+mod downcast;
+pub use downcast::BoxErrorExt;
+
+mod errors;
+pub use errors::ErrorResultExt;
+
+#[cfg(feature = "std")]
+mod escalation;
+#[cfg(feature = "std")]
+pub use escalation::{set_escalation, Escalation};
+
+mod facade;
+
+mod flow;
+pub use flow::{ControlFlowExt, PollExt};
+
+mod filter;
+pub use filter::set_enabled;
+#[cfg(feature = "std")]
+pub use filter::{set_filter, Filter};
+
+#[cfg(feature = "std")]
+mod format;
+
+mod future;
+pub use future::{OptionFutureExt, ResultFutureExt};
 
-use std::fmt;
+#[cfg(feature = "std")]
+mod hook;
+#[cfg(feature = "std")]
+pub use hook::{set_failure_hook, FailureHook};
+
+#[cfg(feature = "std")]
+mod instance;
+#[cfg(feature = "std")]
+pub use instance::set_instance_id;
+#[cfg(feature = "hostname")]
+pub use instance::set_instance_id_from_hostname;
+
+mod iterators;
+pub use iterators::{InspectErrs, ResultIteratorExt};
+
+mod levels;
+pub use levels::{LevelFor, LeveledResultExt};
+#[cfg(feature = "derive")]
+pub use tracing_unwrap_macros::LevelFor;
+
+#[cfg(feature = "std")]
+mod locks;
+#[cfg(feature = "std")]
+pub use locks::{CondvarExt, TryLockResultExt};
+
+#[cfg(feature = "std")]
+mod logged_io;
+#[cfg(feature = "std")]
+pub use logged_io::{LoggedReader, LoggedWriter};
+
+mod lookup;
+pub use lookup::LookupExt;
+
+mod macros;
+#[cfg(feature = "std")]
+pub use format::{json_formatter, set_event_formatter, set_panic_formatter, Formatter};
+
+mod nested;
+pub use nested::OptionResultExt;
+
+#[cfg(feature = "std")]
+mod net;
+#[cfg(feature = "std")]
+pub use net::{TcpListenerExt, TcpStreamExt, UdpSocketExt};
+
+#[cfg(feature = "otel")]
+mod otel;
+
+#[cfg(feature = "std")]
+mod panic_hook;
+#[cfg(feature = "std")]
+pub use panic_hook::install_quiet_panic_hook;
+#[cfg(feature = "panic-hook")]
+pub use panic_hook::{init_panic_hook, init_panic_hook_chained};
+
+#[cfg(feature = "parking_lot")]
+mod parking;
+#[cfg(feature = "parking_lot")]
+pub use parking::{ParkingLotMutexExt, ParkingLotRwLockExt};
+
+#[cfg(feature = "std")]
+mod payload;
+#[cfg(feature = "std")]
+pub use payload::UnwrapFailure;
+
+#[cfg(feature = "std")]
+mod process;
+#[cfg(feature = "std")]
+pub use process::ChildExt;
+
+#[cfg(feature = "std")]
+mod profile;
+#[cfg(feature = "std")]
+pub use profile::{set_debug_defaults, set_release_defaults, ProfileDefaults};
+
+#[cfg(feature = "nightly")]
+mod provide;
+
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "std")]
+pub use rate_limit::{set_rate_limit, RateLimit};
+
+mod record;
+pub use record::FailureRecord;
+
+mod recovery;
+#[cfg(feature = "std")]
+pub use recovery::set_recovery_events;
+
+#[cfg(feature = "heapless")]
+mod bounded;
+#[cfg(feature = "heapless")]
+pub use bounded::{HeaplessIndexMapExt, HeaplessVecExt};
+
+#[cfg(feature = "nb")]
+mod nonblocking;
+#[cfg(feature = "nb")]
+pub use nonblocking::{block_or_log, NbResultExt};
+
+#[cfg(feature = "std")]
+mod reporter;
+#[cfg(feature = "std")]
+pub use reporter::{set_reporter, FailureReporter, TracingReporter};
+
+#[cfg(feature = "std")]
+mod reserve;
+#[cfg(feature = "std")]
+pub use reserve::TryReserveExt;
+
+#[cfg(feature = "tokio")]
+mod retry;
+#[cfg(feature = "tokio")]
+pub use retry::{retry_or_log, Backoff};
+
+#[cfg(feature = "std")]
+mod sampling;
+#[cfg(feature = "std")]
+pub use sampling::set_sampling_priority;
+
+mod shared;
+pub use shared::{ArcExt, RcExt};
+
+#[cfg(feature = "std")]
+mod shutdown;
+#[cfg(feature = "std")]
+pub use shutdown::{set_async_shutdown_hook, set_shutdown_hook};
+
+#[cfg(feature = "backtrace")]
+mod stacktrace;
+
+mod sources;
+pub use sources::set_max_source_depth;
+
+mod span_capture;
+#[cfg(feature = "std")]
+pub use span_capture::{set_async_span_capture, SpanCapture};
+
+mod split;
+pub use split::{Delimiter, StrExt};
+
+#[cfg(feature = "termination-log")]
+mod termination;
+#[cfg(feature = "termination-log")]
+pub use termination::set_termination_log_path;
+
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+#[cfg(feature = "std")]
+mod threads;
+#[cfg(feature = "std")]
+pub use threads::{scope_or_log, BuilderExt, JoinHandleExt};
+
+#[cfg(feature = "tokio")]
+mod timeout;
+#[cfg(feature = "tokio")]
+pub use timeout::{timeout_or_log, try_timeout_or_log};
 
 //
 // Extension trait for Result types.
 //
 
 /// Extension trait for Result types.
+///
+/// Its methods log at fixed levels, unless the error's type declares its own
+/// level with [`LevelFor`] and the `nightly` feature is enabled. On a stable
+/// compiler, see [`LeveledResultExt`] for such errors.
 pub trait ResultExt<T, E> {
     /// Converts `self` into an [`Option<T>`], consuming `self`, and logs the
     /// error, if any, to a [`tracing::Subscriber`] at a [`WARN`] level.
@@ -133,6 +755,124 @@ pub trait ResultExt<T, E> {
     fn expect_err_or_log(self, msg: &str) -> E
     where
         T: fmt::Debug;
+
+    /// Like [`ok_or_log`](ResultExt::ok_or_log), but logs the error, if any,
+    /// at the given `level`, e.g. to demote expected errors to `DEBUG`.
+    fn ok_or_log_at(self, level: tracing::Level) -> Option<T>
+    where
+        E: fmt::Debug;
+
+    /// Like [`unwrap_or_log`](ResultExt::unwrap_or_log), but logs the
+    /// failure at the given `level`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging a message provided by the
+    /// [`Err`]'s value to a [`tracing::Subscriber`] at the given `level`.
+    fn unwrap_or_log_at(self, level: tracing::Level) -> T
+    where
+        E: fmt::Debug;
+
+    /// Like [`expect_or_log`](ResultExt::expect_or_log), but logs the
+    /// failure at the given `level`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging the passed message and the
+    /// content of the [`Err`] to a [`tracing::Subscriber`] at the given
+    /// `level`.
+    fn expect_or_log_at(self, level: tracing::Level, msg: &str) -> T
+    where
+        E: fmt::Debug;
+
+    /// Returns the contained [`Ok`] value or the provided `default`, logging
+    /// the error, if any, to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log_or(self, default: T) -> T
+    where
+        E: fmt::Debug;
+
+    /// Returns the contained [`Ok`] value or computes it from the error with
+    /// `f`, logging the error, if any, to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_else_or_log<F>(self, f: F) -> T
+    where
+        E: fmt::Debug,
+        F: FnOnce(E) -> T;
+
+    /// Returns the contained [`Ok`] value or the default value of `T`,
+    /// logging the error, if any, to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_default_or_log(self) -> T
+    where
+        E: fmt::Debug,
+        T: Default;
+
+    /// Like [`expect_or_log`](ResultExt::expect_or_log), but builds the
+    /// message with `f`, from the error, only if the value is an [`Err`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging the message returned by `f`
+    /// and the content of the [`Err`] to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log_with<F>(self, f: F) -> T
+    where
+        E: fmt::Debug,
+        F: FnOnce(&E) -> String;
+
+    /// Like [`expect_err_or_log`](ResultExt::expect_err_or_log), but builds
+    /// the message with `f`, from the [`Ok`] value, only if the value is an
+    /// [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Ok`], logging the message returned by `f`
+    /// and the content of the [`Ok`] to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_err_or_log_with<F>(self, f: F) -> E
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> String;
+
+    /// Like [`ok_or_log`](ResultExt::ok_or_log), but logs only the type name
+    /// of the error to a [`tracing::Subscriber`] at a [`WARN`] level, never
+    /// its content, so that errors carrying credentials or personal data, or
+    /// not implementing [`Debug`](fmt::Debug), can be logged.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn ok_or_log_opaque(self) -> Option<T>;
+
+    /// Like [`unwrap_or_log`](ResultExt::unwrap_or_log), but logs only the
+    /// type name of the error, never its content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging a message with the type
+    /// name of the error to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log_opaque(self) -> T;
+
+    /// Like [`expect_or_log`](ResultExt::expect_or_log), but logs only the
+    /// passed message, never the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`Err`], logging the passed message to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log_redacted(self, msg: &str) -> T;
 }
 
 impl<T, E> ResultExt<T, E> for Result<T, E> {
@@ -142,10 +882,21 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     where
         E: fmt::Debug,
     {
+        callsite::register("Result::ok_or_log");
         match self {
-            Ok(t) => Some(t),
+            Ok(t) => {
+                recovery::succeeded("Result::ok_or_log", core::panic::Location::caller());
+                Some(t)
+            }
             Err(e) => {
-                discarded_with("called `Result::ok_or_log` on an `Err` value", &e);
+                err_discarded_at(
+                    facade::Level::WARN,
+                    levels::declared(&e),
+                    "Result::ok_or_log",
+                    "called `Result::ok_or_log` on an `Err` value",
+                    &e,
+                    core::any::type_name::<E>(),
+                );
                 None
             }
         }
@@ -157,9 +908,17 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     where
         E: fmt::Debug,
     {
+        callsite::register("Result::unwrap_or_log");
         match self {
             Ok(t) => t,
-            Err(e) => failed_with("called `Result::unwrap_or_log()` on an `Err` value", &e),
+            Err(e) => err_failed_at(
+                facade::Level::ERROR,
+                levels::declared(&e),
+                "Result::unwrap_or_log",
+                "called `Result::unwrap_or_log()` on an `Err` value",
+                &e,
+                core::any::type_name::<E>(),
+            ),
         }
     }
 
@@ -169,9 +928,17 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     where
         E: fmt::Debug,
     {
+        callsite::register("Result::expect_or_log");
         match self {
             Ok(t) => t,
-            Err(e) => failed_with(msg, &e),
+            Err(e) => err_failed_at(
+                facade::Level::ERROR,
+                levels::declared(&e),
+                "Result::expect_or_log",
+                msg,
+                &e,
+                core::any::type_name::<E>(),
+            ),
         }
     }
 
@@ -181,8 +948,13 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     where
         T: fmt::Debug,
     {
+        callsite::register("Result::unwrap_err_or_log");
         match self {
-            Ok(t) => failed_with("called `Result::unwrap_err_or_log()` on an `Ok` value", &t),
+            Ok(t) => failed_with(
+                "Result::unwrap_err_or_log",
+                "called `Result::unwrap_err_or_log()` on an `Ok` value",
+                &t,
+            ),
             Err(e) => e,
         }
     }
@@ -193,11 +965,258 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
     where
         T: fmt::Debug,
     {
+        callsite::register("Result::expect_err_or_log");
+        match self {
+            Ok(t) => failed_with("Result::expect_err_or_log", msg, &t),
+            Err(e) => e,
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn ok_or_log_at(self, level: tracing::Level) -> Option<T>
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("Result::ok_or_log_at");
+        match self {
+            Ok(t) => {
+                recovery::succeeded("Result::ok_or_log_at", core::panic::Location::caller());
+                Some(t)
+            }
+            Err(e) => {
+                err_discarded_at_chosen(
+                    level,
+                    "Result::ok_or_log_at",
+                    "called `Result::ok_or_log_at` on an `Err` value",
+                    &e,
+                    core::any::type_name::<E>(),
+                );
+                None
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_at(self, level: tracing::Level) -> T
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("Result::unwrap_or_log_at");
+        match self {
+            Ok(t) => t,
+            Err(e) => err_failed_at_chosen(
+                level,
+                "Result::unwrap_or_log_at",
+                "called `Result::unwrap_or_log_at()` on an `Err` value",
+                &e,
+                core::any::type_name::<E>(),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_at(self, level: tracing::Level, msg: &str) -> T
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("Result::expect_or_log_at");
+        match self {
+            Ok(t) => t,
+            Err(e) => err_failed_at_chosen(
+                level,
+                "Result::expect_or_log_at",
+                msg,
+                &e,
+                core::any::type_name::<E>(),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_or(self, default: T) -> T
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("Result::unwrap_or_log_or");
+        match self {
+            Ok(t) => {
+                recovery::succeeded("Result::unwrap_or_log_or", core::panic::Location::caller());
+                t
+            }
+            Err(e) => {
+                err_discarded_at(
+                    facade::Level::ERROR,
+                    levels::declared(&e),
+                    "Result::unwrap_or_log_or",
+                    "called `Result::unwrap_or_log_or()` on an `Err` value",
+                    &e,
+                    core::any::type_name::<E>(),
+                );
+                default
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_else_or_log<F>(self, f: F) -> T
+    where
+        E: fmt::Debug,
+        F: FnOnce(E) -> T,
+    {
+        callsite::register("Result::unwrap_or_else_or_log");
+        match self {
+            Ok(t) => {
+                recovery::succeeded(
+                    "Result::unwrap_or_else_or_log",
+                    core::panic::Location::caller(),
+                );
+                t
+            }
+            Err(e) => {
+                err_discarded_at(
+                    facade::Level::ERROR,
+                    levels::declared(&e),
+                    "Result::unwrap_or_else_or_log",
+                    "called `Result::unwrap_or_else_or_log()` on an `Err` value",
+                    &e,
+                    core::any::type_name::<E>(),
+                );
+                f(e)
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_default_or_log(self) -> T
+    where
+        E: fmt::Debug,
+        T: Default,
+    {
+        callsite::register("Result::unwrap_or_default_or_log");
+        match self {
+            Ok(t) => {
+                recovery::succeeded(
+                    "Result::unwrap_or_default_or_log",
+                    core::panic::Location::caller(),
+                );
+                t
+            }
+            Err(e) => {
+                err_discarded_at(
+                    facade::Level::ERROR,
+                    levels::declared(&e),
+                    "Result::unwrap_or_default_or_log",
+                    "called `Result::unwrap_or_default_or_log()` on an `Err` value",
+                    &e,
+                    core::any::type_name::<E>(),
+                );
+                T::default()
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_with<F>(self, f: F) -> T
+    where
+        E: fmt::Debug,
+        F: FnOnce(&E) -> String,
+    {
+        callsite::register("Result::expect_or_log_with");
+        match self {
+            Ok(t) => t,
+            Err(e) => err_failed_at(
+                facade::Level::ERROR,
+                levels::declared(&e),
+                "Result::expect_or_log_with",
+                &f(&e),
+                &e,
+                core::any::type_name::<E>(),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_err_or_log_with<F>(self, f: F) -> E
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> String,
+    {
+        callsite::register("Result::expect_err_or_log_with");
         match self {
-            Ok(t) => failed_with(msg, &t),
+            Ok(t) => failed_with("Result::expect_err_or_log_with", &f(&t), &t),
             Err(e) => e,
         }
     }
+
+    #[inline]
+    #[track_caller]
+    fn ok_or_log_opaque(self) -> Option<T> {
+        callsite::register("Result::ok_or_log_opaque");
+        match self {
+            Ok(t) => {
+                recovery::succeeded("Result::ok_or_log_opaque", core::panic::Location::caller());
+                Some(t)
+            }
+            Err(e) => {
+                err_discarded_at(
+                    facade::Level::WARN,
+                    levels::declared(&e),
+                    "Result::ok_or_log_opaque",
+                    "called `Result::ok_or_log_opaque` on an `Err` value",
+                    &TypeName(core::any::type_name::<E>()),
+                    core::any::type_name::<E>(),
+                );
+                None
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_opaque(self) -> T {
+        callsite::register("Result::unwrap_or_log_opaque");
+        match self {
+            Ok(t) => t,
+            Err(e) => err_failed_at(
+                facade::Level::ERROR,
+                levels::declared(&e),
+                "Result::unwrap_or_log_opaque",
+                "called `Result::unwrap_or_log_opaque()` on an `Err` value",
+                &TypeName(core::any::type_name::<E>()),
+                core::any::type_name::<E>(),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_redacted(self, msg: &str) -> T {
+        callsite::register("Result::expect_or_log_redacted");
+        match self {
+            Ok(t) => t,
+            Err(e) => match levels::declared(&e) {
+                Some(level) => failed_at_chosen(level, "Result::expect_or_log_redacted", msg),
+                None => failed("Result::expect_or_log_redacted", msg),
+            },
+        }
+    }
+}
+
+/// Renders the type name of an error in place of its content.
+struct TypeName(&'static str);
+
+impl fmt::Debug for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
 }
 
 //
@@ -245,24 +1264,98 @@ pub trait OptionExt<T> {
     fn expect_none_or_log(self, msg: &str)
     where
         T: fmt::Debug;
+
+    /// Like [`unwrap_or_log`](OptionExt::unwrap_or_log), but logs the
+    /// failure at the given `level`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the self value equals [`None`], logging an error message to a
+    /// [`tracing::Subscriber`] at the given `level`.
+    fn unwrap_or_log_at(self, level: tracing::Level) -> T;
+
+    /// Like [`expect_or_log`](OptionExt::expect_or_log), but logs the
+    /// failure at the given `level`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`None`], logging the passed message to a
+    /// [`tracing::Subscriber`] at the given `level`.
+    fn expect_or_log_at(self, level: tracing::Level, msg: &str) -> T;
+
+    /// Returns the contained [`Some`] value or the provided `default`,
+    /// logging a [`None`] to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log_or(self, default: T) -> T;
+
+    /// Returns the contained [`Some`] value or computes it with `f`, logging
+    /// a [`None`] to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_else_or_log<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T;
+
+    /// Returns the contained [`Some`] value or the default value of `T`,
+    /// logging a [`None`] to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_default_or_log(self) -> T
+    where
+        T: Default;
+
+    /// Like [`expect_or_log`](OptionExt::expect_or_log), but builds the
+    /// message with `f` only if the value is a [`None`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`None`], logging the message returned by `f`
+    /// to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log_with<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> String;
+
+    /// Like [`expect_none_or_log`](OptionExt::expect_none_or_log), but builds
+    /// the message with `f`, from the [`Some`] value, only if the value is a
+    /// [`Some`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a [`Some`], logging the message returned by `f`
+    /// and the content of the [`Some`] to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_none_or_log_with<F>(self, f: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> String;
 }
 
 impl<T> OptionExt<T> for Option<T> {
     #[inline]
     #[track_caller]
     fn unwrap_or_log(self) -> T {
+        callsite::register("Option::unwrap_or_log");
         match self {
             Some(val) => val,
-            None => failed("called `Option::unwrap_or_log()` on a `None` value"),
+            None => failed(
+                "Option::unwrap_or_log",
+                "called `Option::unwrap_or_log()` on a `None` value",
+            ),
         }
     }
 
     #[inline]
     #[track_caller]
     fn expect_or_log(self, msg: &str) -> T {
+        callsite::register("Option::expect_or_log");
         match self {
             Some(val) => val,
-            None => failed(msg),
+            None => failed("Option::expect_or_log", msg),
         }
     }
 
@@ -272,8 +1365,10 @@ impl<T> OptionExt<T> for Option<T> {
     where
         T: fmt::Debug,
     {
+        callsite::register("Option::unwrap_none_or_log");
         if let Some(val) = self {
             failed_with(
+                "Option::unwrap_none_or_log",
                 "called `Option::unwrap_none_or_log()` on a `Some` value",
                 &val,
             );
@@ -286,8 +1381,131 @@ impl<T> OptionExt<T> for Option<T> {
     where
         T: fmt::Debug,
     {
+        callsite::register("Option::expect_none_or_log");
         if let Some(val) = self {
-            failed_with(msg, &val);
+            failed_with("Option::expect_none_or_log", msg, &val);
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_at(self, level: tracing::Level) -> T {
+        callsite::register("Option::unwrap_or_log_at");
+        match self {
+            Some(val) => val,
+            None => failed_at_chosen(
+                level,
+                "Option::unwrap_or_log_at",
+                "called `Option::unwrap_or_log_at()` on a `None` value",
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_at(self, level: tracing::Level, msg: &str) -> T {
+        callsite::register("Option::expect_or_log_at");
+        match self {
+            Some(val) => val,
+            None => failed_at_chosen(level, "Option::expect_or_log_at", msg),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log_or(self, default: T) -> T {
+        callsite::register("Option::unwrap_or_log_or");
+        match self {
+            Some(val) => {
+                recovery::succeeded("Option::unwrap_or_log_or", core::panic::Location::caller());
+                val
+            }
+            None => {
+                discarded_at(
+                    facade::Level::ERROR,
+                    "Option::unwrap_or_log_or",
+                    "called `Option::unwrap_or_log_or()` on a `None` value",
+                );
+                default
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_else_or_log<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        callsite::register("Option::unwrap_or_else_or_log");
+        match self {
+            Some(val) => {
+                recovery::succeeded(
+                    "Option::unwrap_or_else_or_log",
+                    core::panic::Location::caller(),
+                );
+                val
+            }
+            None => {
+                discarded_at(
+                    facade::Level::ERROR,
+                    "Option::unwrap_or_else_or_log",
+                    "called `Option::unwrap_or_else_or_log()` on a `None` value",
+                );
+                f()
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_default_or_log(self) -> T
+    where
+        T: Default,
+    {
+        callsite::register("Option::unwrap_or_default_or_log");
+        match self {
+            Some(val) => {
+                recovery::succeeded(
+                    "Option::unwrap_or_default_or_log",
+                    core::panic::Location::caller(),
+                );
+                val
+            }
+            None => {
+                discarded_at(
+                    facade::Level::ERROR,
+                    "Option::unwrap_or_default_or_log",
+                    "called `Option::unwrap_or_default_or_log()` on a `None` value",
+                );
+                T::default()
+            }
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log_with<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> String,
+    {
+        callsite::register("Option::expect_or_log_with");
+        match self {
+            Some(val) => val,
+            None => failed("Option::expect_or_log_with", &f()),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_none_or_log_with<F>(self, f: F)
+    where
+        T: fmt::Debug,
+        F: FnOnce(&T) -> String,
+    {
+        callsite::register("Option::expect_none_or_log_with");
+        if let Some(val) = self {
+            failed_with("Option::expect_none_or_log_with", &f(&val), &val);
         }
     }
 }
@@ -296,74 +1514,504 @@ impl<T> OptionExt<T> for Option<T> {
 // Helper functions.
 //
 
+/// Helpers called from the expansion of this crate's macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+    #[cfg(feature = "std")]
+    pub use crate::context::{enter as enter_context, in_context, ContextGuard, InContext};
+    pub use crate::facade::Level;
+    #[cfg(feature = "std")]
+    pub use crate::macros::Scope;
+    pub use crate::macros::{Check, Failures, Unwrap};
+
+    #[inline(always)]
+    #[track_caller]
+    pub fn register(method: &'static str) {
+        callsite::register(method)
+    }
+
+    #[inline(never)]
+    #[cold]
+    #[track_caller]
+    pub fn mismatched(msg: &str, pattern: &str, value: &dyn fmt::Debug) -> ! {
+        fail(
+            &FailureRecord::new(
+                "matches_or_log!",
+                msg,
+                Some(value),
+                facade::Level::ERROR,
+                true,
+            )
+            .with_pattern(pattern),
+        )
+    }
+
+    #[inline(never)]
+    #[cold]
+    #[track_caller]
+    pub fn guard_failed(level: facade::Level, condition: &str) {
+        let msg = format!("guard `{}` failed", condition);
+        emit(
+            &FailureRecord::new("guard_or_log!", &msg, None, level, false)
+                .with_condition(condition)
+                .with_caller_level(),
+        );
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn select_branch_or_log<T, E: fmt::Debug>(label: &str, output: Result<T, E>) -> T {
+        match output {
+            Ok(t) => t,
+            Err(e) => super::failed_with(
+                "select_or_log!",
+                &format!("select branch `{}` failed", label),
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn select_branch_or_propagate<T, E: fmt::Debug>(
+        label: &str,
+        output: Result<T, E>,
+    ) -> Result<T, E> {
+        if let Err(e) = &output {
+            super::discarded_with(
+                "try_select_or_log!",
+                &format!("select branch `{}` failed", label),
+                e,
+            );
+        }
+        output
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn failed(method: &'static str, msg: &str) -> ! {
+        super::failed(method, msg)
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn failed_with(method: &'static str, msg: &str, value: &dyn fmt::Debug) -> ! {
+        super::failed_with(method, msg, value)
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn discarded(method: &'static str, msg: &str) {
+        super::discarded(method, msg)
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn discarded_with(method: &'static str, msg: &str, value: &dyn fmt::Debug) {
+        super::discarded_with(method, msg, value)
+    }
+}
+
+#[inline]
+#[track_caller]
+fn failed(method: &'static str, msg: &str) -> ! {
+    failed_at(facade::Level::ERROR, method, msg)
+}
+
 #[inline(never)]
 #[cold]
 #[track_caller]
-fn failed(msg: &str) -> ! {
-    #[cfg(feature = "log-location")]
-    {
-        let location = std::panic::Location::caller();
-        tracing::error!(
-            unwrap.filepath = location.file(),
-            unwrap.lineno = location.line(),
-            unwrap.columnno = location.column(),
-            "{}",
-            msg
-        );
-    }
+fn failed_at(level: facade::Level, method: &'static str, msg: &str) -> ! {
+    fail(&FailureRecord::new(method, msg, None, level, true))
+}
 
-    #[cfg(not(feature = "log-location"))]
-    tracing::error!("{}", msg);
+#[inline]
+#[track_caller]
+fn failed_with(method: &'static str, msg: &str, value: &dyn fmt::Debug) -> ! {
+    failed_with_at(facade::Level::ERROR, method, msg, value)
+}
 
-    #[cfg(feature = "panic-quiet")]
-    panic!();
-    #[cfg(not(feature = "panic-quiet"))]
-    panic!("{}", msg)
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn failed_with_at(
+    level: facade::Level,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+) -> ! {
+    fail(&FailureRecord::new(method, msg, Some(value), level, true))
+}
+
+#[inline]
+#[track_caller]
+fn discarded(method: &'static str, msg: &str) {
+    discarded_at(facade::Level::WARN, method, msg)
 }
 
 #[inline(never)]
 #[cold]
 #[track_caller]
-fn failed_with(msg: &str, value: &dyn fmt::Debug) -> ! {
-    #[cfg(feature = "log-location")]
-    {
-        let location = std::panic::Location::caller();
-        tracing::error!(
-            unwrap.filepath = location.file(),
-            unwrap.lineno = location.line(),
-            unwrap.columnno = location.column(),
-            "{}: {:?}",
-            msg,
-            &value
-        );
+fn discarded_at(level: facade::Level, method: &'static str, msg: &str) {
+    emit(&FailureRecord::new(method, msg, None, level, false));
+}
+
+#[inline]
+#[track_caller]
+fn discarded_with(method: &'static str, msg: &str, value: &dyn fmt::Debug) {
+    discarded_with_at(facade::Level::WARN, method, msg, value)
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn discarded_with_at(
+    level: facade::Level,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+) {
+    emit(&FailureRecord::new(method, msg, Some(value), level, false));
+}
+
+/// Like [`failed_with_at`], for the error of a [`Result`], whose type name is
+/// recorded as well. The level the error `declared`, if any, replaces `level`.
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn err_failed_at(
+    level: facade::Level,
+    declared: Option<facade::Level>,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+    error_type: &'static str,
+) -> ! {
+    match declared {
+        Some(level) => err_failed_at_chosen(level, method, msg, value, error_type),
+        None => fail(
+            &FailureRecord::new(method, msg, Some(value), level, true).with_error_type(error_type),
+        ),
+    }
+}
+
+/// Like [`discarded_with_at`], for the error of a [`Result`], whose type name
+/// is recorded as well. The level the error `declared`, if any, replaces
+/// `level`.
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn err_discarded_at(
+    level: facade::Level,
+    declared: Option<facade::Level>,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+    error_type: &'static str,
+) {
+    match declared {
+        Some(level) => err_discarded_at_chosen(level, method, msg, value, error_type),
+        None => {
+            emit(
+                &FailureRecord::new(method, msg, Some(value), level, false)
+                    .with_error_type(error_type),
+            );
+        }
     }
+}
 
-    #[cfg(not(feature = "log-location"))]
-    tracing::error!("{}: {:?}", msg, &value);
+/// Like [`failed_at`], for a level chosen at the call site, which profile
+/// defaults leave as it is.
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn failed_at_chosen(level: facade::Level, method: &'static str, msg: &str) -> ! {
+    fail(&FailureRecord::new(method, msg, None, level, true).with_caller_level())
+}
 
-    #[cfg(feature = "panic-quiet")]
-    panic!();
-    #[cfg(not(feature = "panic-quiet"))]
-    panic!("{}: {:?}", msg, &value);
+/// Like [`err_failed_at`], for a level chosen at the call site.
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn err_failed_at_chosen(
+    level: facade::Level,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+    error_type: &'static str,
+) -> ! {
+    fail(
+        &FailureRecord::new(method, msg, Some(value), level, true)
+            .with_error_type(error_type)
+            .with_caller_level(),
+    )
 }
 
+/// Like [`err_discarded_at`], for a level chosen at the call site.
 #[inline(never)]
 #[cold]
 #[track_caller]
-fn discarded_with(msg: &str, value: &dyn fmt::Debug) {
-    #[cfg(feature = "log-location")]
+fn err_discarded_at_chosen(
+    level: facade::Level,
+    method: &'static str,
+    msg: &str,
+    value: &dyn fmt::Debug,
+    error_type: &'static str,
+) {
+    emit(
+        &FailureRecord::new(method, msg, Some(value), level, false)
+            .with_error_type(error_type)
+            .with_caller_level(),
+    );
+}
+
+/// Emits the event for a fatal failure, then dies with the record as it was
+/// logged.
+#[track_caller]
+fn fail(record: &FailureRecord<'_>) -> ! {
+    let record = emit(record);
+    die(&record)
+}
+
+/// Runs the shutdown hook, then panics, unless the [`FailureBehavior`] is to
+/// abort or exit.
+///
+/// With the `panic-payload` feature and no panic formatter, the panic payload
+/// is an [`UnwrapFailure`] rather than a message.
+///
+/// If the thread is already unwinding, panicking again would abort the process
+/// with the panic hook's output for this failure lost, so it aborts right away
+/// instead, with the failure event marked as having happened during unwind. It
+/// also aborts once a circuit breaker for fatal failures has tripped.
+#[track_caller]
+fn die(record: &FailureRecord<'_>) -> ! {
+    #[cfg(feature = "termination-log")]
+    termination::write(record);
+
+    #[cfg(feature = "std")]
     {
-        let location = std::panic::Location::caller();
-        tracing::warn!(
-            unwrap.filepath = location.file(),
-            unwrap.lineno = location.line(),
-            unwrap.columnno = location.column(),
-            "{}: {:?}",
-            msg,
-            &value
-        );
+        shutdown::run();
+
+        if record.is_during_unwind() || (record.is_fatal() && breaker::trips(record)) {
+            std::process::abort();
+        }
+
+        behavior::apply();
+
+        if let Some(msg) = format::panic_message(record) {
+            panic!("{}", msg);
+        }
+    }
+
+    #[cfg(feature = "panic-payload")]
+    std::panic::panic_any(UnwrapFailure::from(record));
+
+    #[cfg(not(feature = "panic-payload"))]
+    {
+        #[cfg(feature = "panic-quiet")]
+        {
+            let _ = record;
+            panic!();
+        }
+        #[cfg(not(feature = "panic-quiet"))]
+        panic!("{}", record);
+    }
+}
+
+/// Reports a failure at its escalated level, unless the caller's location is
+/// filtered out or rate limited, then runs the failure hook. Returns the
+/// record as it was reported.
+///
+/// A discarded error dies like a fatal failure once a circuit breaker for
+/// discarded errors has tripped.
+#[cfg(feature = "std")]
+#[track_caller]
+fn emit<'a>(record: &FailureRecord<'a>) -> FailureRecord<'a> {
+    let record = &escalation::escalate(&callsite::track(&profile::apply(record)));
+    let tripped = !record.is_fatal() && breaker::trips(record);
+
+    if filter::enabled(record.level(), record.location()) {
+        if rate_limit::admit(record) && (record.is_fatal() || tripped || !buffer::capture(record)) {
+            reporter::report(record);
+        }
+        recovery::failed(record);
+        if record.is_fatal() || tripped {
+            panic_hook::mark_logged();
+        }
     }
 
-    #[cfg(not(feature = "log-location"))]
-    tracing::warn!("{}: {:?}", msg, &value);
+    hook::run(record);
+
+    if tripped {
+        die(record);
+    }
+    *record
+}
+
+/// Emits the tracing event for a failure, unless events are disabled.
+///
+/// Without the standard library, none of the policies that configure
+/// failures at runtime are available.
+#[cfg(not(feature = "std"))]
+#[track_caller]
+fn emit<'a>(record: &FailureRecord<'a>) -> FailureRecord<'a> {
+    if filter::enabled(record.level(), record.location()) {
+        log(record);
+    }
+    *record
+}
+
+/// The target of the events of this crate: the value of the
+/// `TRACING_UNWRAP_TARGET` environment variable at build time, if set, or
+/// `tracing_unwrap`.
+///
+/// `tracing` records targets in the static metadata of each callsite, so it
+/// can only be chosen at build time.
+const TARGET: &str = match option_env!("TRACING_UNWRAP_TARGET") {
+    Some(target) => target,
+    None => "tracing_unwrap",
+};
+
+/// Emits the tracing event for a failure, as the default reporter.
+fn log(record: &FailureRecord<'_>) {
+    #[cfg(feature = "std")]
+    let msg = match format::event_message(record) {
+        Some(msg) => msg,
+        None => record.to_string(),
+    };
+    #[cfg(not(feature = "std"))]
+    let msg = record.to_string();
+
+    #[cfg(feature = "test-util")]
+    test_util::capture(record, &msg);
+
+    let prefix = if record.is_during_unwind() {
+        "failure during unwind: "
+    } else {
+        ""
+    };
+    let level = record.level();
+    // The value is recorded as the `error` field too, as an error when it is
+    // known to be one, so that its source chain is captured. Subscribers walk
+    // that chain in full, so a chain that is too deep for `unwrap.sources` is
+    // only recorded as a value.
+    #[cfg(all(feature = "structured-errors", feature = "std"))]
+    match record.error().filter(|e| sources::within_max_depth(*e)) {
+        Some(error) => {
+            dispatch::with_dispatch(|| event_at!(level, record, error = error, "{}{}", prefix, msg))
+        }
+        None => dispatch::with_dispatch(|| {
+            let value = record.value().map(facade::field::debug);
+            event_at!(level, record, error = value, "{}{}", prefix, msg)
+        }),
+    }
+    // Without the standard library, `tracing` can't record error values, so
+    // errors are recorded with their `Debug` representation as well; their
+    // sources are still rendered as `unwrap.sources`.
+    #[cfg(all(feature = "structured-errors", not(feature = "std")))]
+    dispatch::with_dispatch(|| {
+        let value = record.value().map(facade::field::debug);
+        event_at!(level, record, error = value, "{}{}", prefix, msg)
+    });
+    #[cfg(not(feature = "structured-errors"))]
+    dispatch::with_dispatch(|| event_at!(level, record, "{}{}", prefix, msg));
+}
+
+/// Like `tracing::event!`, but takes a runtime `level` and adds the fields
+/// describing a [`FailureRecord`].
+macro_rules! event_at {
+    ($level:expr, $record:expr, $($arg:tt)+) => {
+        match $level {
+            facade::Level::ERROR => event_at!(@ facade::Level::ERROR, $record, $($arg)+),
+            facade::Level::WARN => event_at!(@ facade::Level::WARN, $record, $($arg)+),
+            facade::Level::INFO => event_at!(@ facade::Level::INFO, $record, $($arg)+),
+            facade::Level::DEBUG => event_at!(@ facade::Level::DEBUG, $record, $($arg)+),
+            facade::Level::TRACE => event_at!(@ facade::Level::TRACE, $record, $($arg)+),
+        }
+    };
+    (@ $level:expr, $record:expr, $($arg:tt)+) => {{
+        let record: &FailureRecord<'_> = $record;
+        // Location fields are only recorded with the `log-location` feature.
+        let location = cfg!(feature = "log-location").then(|| record.location());
+        // With the `otel-fields` feature, the location is recorded under the
+        // names of OpenTelemetry's semantic conventions instead.
+        let (location, code_location) = if cfg!(feature = "otel-fields") {
+            (None, location)
+        } else {
+            (location, None)
+        };
+        let exception = cfg!(feature = "otel-fields").then_some(record);
+        let sources = record.error().and_then(sources::render);
+        #[cfg(feature = "std")]
+        let instance_id = instance::current();
+        #[cfg(not(feature = "std"))]
+        let instance_id: Option<alloc::sync::Arc<str>> = None;
+        #[cfg(feature = "build-metadata")]
+        let build = build_info::current();
+        #[cfg(feature = "build-metadata")]
+        let (commit, profile, timestamp) = match build.as_deref() {
+            Some(build) => (build.commit(), build.profile(), build.timestamp()),
+            None => (None, None, None),
+        };
+        #[cfg(not(feature = "build-metadata"))]
+        let (commit, profile, timestamp): (Option<&str>, Option<&str>, Option<&str>) =
+            (None, None, None);
+        // A backtrace captured along with a buffered failure, or an error's
+        // own backtrace, if it provides one, makes a capture moot.
+        let provided = record.stacktrace().map(String::from);
+        #[cfg(feature = "nightly")]
+        let provided = provided.or_else(|| record.error().and_then(provide::backtrace));
+        #[cfg(feature = "backtrace")]
+        let stacktrace = match provided {
+            Some(provided) => Some(provided),
+            // Called directly, as a combinator would add `core` frames that
+            // aren't trimmed.
+            None => stacktrace::capture(),
+        };
+        #[cfg(not(feature = "backtrace"))]
+        let stacktrace = provided;
+        #[cfg(feature = "otel")]
+        let baggage = otel::baggage();
+        #[cfg(not(feature = "otel"))]
+        let baggage: Option<String> = None;
+        #[cfg(feature = "std")]
+        let (sampling_priority, context, error_kind) =
+            (sampling::priority(record), context::current(), record.error_kind());
+        #[cfg(not(feature = "std"))]
+        let (sampling_priority, context, error_kind): (Option<i64>, Option<String>, Option<()>) =
+            (None, None, None);
+        facade::event!(
+            target: TARGET,
+            $level,
+            unwrap.filepath = location.map(|l| l.file()),
+            unwrap.lineno = location.map(|l| l.line()),
+            unwrap.columnno = location.map(|l| l.column()),
+            // `tracing` allows at most 32 fields per event, so the details
+            // that only some failures have share a single field.
+            unwrap.details = record.details().map(facade::field::debug),
+            unwrap.during_unwind = record.is_during_unwind().then_some(true),
+            unwrap.callsite_id = %format_args!("{:016x}", record.callsite_id()),
+            unwrap.method = record.method(),
+            unwrap.stacktrace = stacktrace.as_deref(),
+            unwrap.sources = sources.as_ref().map(|s| s.chain.as_str()),
+            unwrap.sources_truncated = sources.as_ref().and_then(|s| s.truncated_at),
+            unwrap.error_kind = error_kind.map(facade::field::debug),
+            unwrap.instance_id = instance_id.as_deref(),
+            unwrap.build.commit = commit,
+            unwrap.build.profile = profile,
+            unwrap.build.timestamp = timestamp,
+            unwrap.baggage = baggage.as_deref(),
+            sampling.priority = sampling_priority,
+            unwrap.context = context.as_deref(),
+            unwrap.occurrence = record.occurrence(),
+            code.filepath = code_location.map(|l| l.file()),
+            code.lineno = code_location.map(|l| l.line()),
+            code.column = code_location.map(|l| l.column()),
+            exception.message = exception.map(facade::field::display),
+            exception.r#type = exception.and_then(|r| r.error_type()),
+            $($arg)+
+        );
+    }};
 }
+use event_at;
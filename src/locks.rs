@@ -0,0 +1,179 @@
+//! Extensions for non-blocking lock attempts and condition variables.
+
+use crate::{callsite, discarded_with, failed, recovery};
+use std::panic::Location;
+use std::sync::{Condvar, MutexGuard, TryLockError, TryLockResult, WaitTimeoutResult};
+use std::time::{Duration, Instant};
+
+/// Extension trait for [`TryLockResult`] values, as returned by
+/// [`Mutex::try_lock`](std::sync::Mutex::try_lock) and the `try_read` and
+/// `try_write` methods of [`RwLock`](std::sync::RwLock).
+///
+/// Contention and poisoning call for completely different responses, so they
+/// are logged distinctly: contention at a [`WARN`] level, poisoning at an
+/// [`ERROR`] level.
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+pub trait TryLockResultExt<G> {
+    /// Unwraps the lock attempt, yielding the guard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is held elsewhere, logging the contention to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level, or if the lock is
+    /// poisoned, logging the poisoning at an [`ERROR`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn lock_or_log(self) -> G;
+
+    /// Converts the lock attempt into an [`Option`] of the guard, logging the
+    /// contention, if any, to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock is poisoned, logging the poisoning at an [`ERROR`]
+    /// level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn try_lock_or_log(self) -> Option<G>;
+}
+
+impl<G> TryLockResultExt<G> for TryLockResult<G> {
+    #[inline]
+    #[track_caller]
+    fn lock_or_log(self) -> G {
+        callsite::register("TryLockResult::lock_or_log");
+        match self {
+            Ok(guard) => guard,
+            Err(TryLockError::WouldBlock) => contended(
+                "TryLockResult::lock_or_log",
+                "called `TryLockResult::lock_or_log()` on a lock held elsewhere",
+            ),
+            Err(TryLockError::Poisoned(_)) => failed(
+                "TryLockResult::lock_or_log",
+                "called `TryLockResult::lock_or_log()` on a poisoned lock",
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn try_lock_or_log(self) -> Option<G> {
+        callsite::register("TryLockResult::try_lock_or_log");
+        match self {
+            Ok(guard) => {
+                recovery::succeeded("TryLockResult::try_lock_or_log", Location::caller());
+                Some(guard)
+            }
+            Err(TryLockError::WouldBlock) => {
+                crate::discarded(
+                    "TryLockResult::try_lock_or_log",
+                    "called `TryLockResult::try_lock_or_log()` on a lock held elsewhere",
+                );
+                None
+            }
+            Err(TryLockError::Poisoned(_)) => failed(
+                "TryLockResult::try_lock_or_log",
+                "called `TryLockResult::try_lock_or_log()` on a poisoned lock",
+            ),
+        }
+    }
+}
+
+/// Like [`failed`](crate::failed), but logs at a `WARN` level, as contention
+/// is not an error in itself.
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn contended(method: &'static str, msg: &str) -> ! {
+    crate::fail(&crate::FailureRecord::new(
+        method,
+        msg,
+        None,
+        crate::facade::Level::WARN,
+        true,
+    ))
+}
+
+/// Extension trait for [`Condvar`]s.
+///
+/// Unwrapping the results of waits hides the difference between a poisoned
+/// mutex and a timed-out wait, which often points to a deadlock, so they are
+/// logged distinctly.
+pub trait CondvarExt {
+    /// Blocks until notified, like [`Condvar::wait`], yielding the guard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned, logging the poisoning to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn wait_or_log<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T>;
+
+    /// Blocks until notified or until `timeout` elapses, like
+    /// [`Condvar::wait_timeout`], yielding the guard and whether the wait
+    /// timed out.
+    ///
+    /// A timeout is logged, with the time actually waited, to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned, logging the poisoning at an [`ERROR`]
+    /// level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn wait_timeout_or_log<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout: Duration,
+    ) -> (MutexGuard<'a, T>, WaitTimeoutResult);
+}
+
+impl CondvarExt for Condvar {
+    #[inline]
+    #[track_caller]
+    fn wait_or_log<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        callsite::register("Condvar::wait_or_log");
+        match self.wait(guard) {
+            Ok(guard) => guard,
+            Err(_) => failed(
+                "Condvar::wait_or_log",
+                "called `Condvar::wait_or_log()` on a poisoned mutex",
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn wait_timeout_or_log<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout: Duration,
+    ) -> (MutexGuard<'a, T>, WaitTimeoutResult) {
+        callsite::register("Condvar::wait_timeout_or_log");
+        let started = Instant::now();
+        match self.wait_timeout(guard, timeout) {
+            Ok((guard, result)) => {
+                if result.timed_out() {
+                    let waited = started.elapsed();
+                    discarded_with(
+                        "Condvar::wait_timeout_or_log",
+                        &format!("wait timed out after {:?}", timeout),
+                        &format_args!("waited {:?}", waited),
+                    );
+                }
+                (guard, result)
+            }
+            Err(_) => failed(
+                "Condvar::wait_timeout_or_log",
+                "called `Condvar::wait_timeout_or_log()` on a poisoned mutex",
+            ),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! Readers and writers that log their I/O errors.
+
+use crate::{callsite, emit, fail, FailureRecord};
+use std::io::{self, Read, Write};
+use std::panic::Location;
+
+/// A [`Read`] wrapper that logs the errors of the inner reader, with an
+/// operation label and the number of bytes read so far, to a
+/// [`tracing::Subscriber`] at a [`WARN`] level.
+///
+/// Errors are still returned, so `read_exact(...).unwrap()` and friends keep
+/// working, now with context in the logs. Call
+/// [`panicking`](Self::panicking) to panic on errors instead, logging them at
+/// an [`ERROR`] level. Interrupted reads are retried by callers, and not
+/// logged.
+///
+/// ```
+/// use std::io::Read;
+/// use tracing_unwrap::LoggedReader;
+///
+/// let mut reader = LoggedReader::new(&b"header"[..], "read header");
+/// let mut header = [0; 6];
+/// reader.read_exact(&mut header).unwrap();
+/// ```
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[derive(Debug)]
+pub struct LoggedReader<R> {
+    inner: R,
+    label: String,
+    bytes: u64,
+    fatal: bool,
+    location: &'static Location<'static>,
+}
+
+impl<R> LoggedReader<R> {
+    /// Wraps `inner`, labelling its errors with `label`. The location of this
+    /// call is reported for the errors.
+    #[track_caller]
+    pub fn new(inner: R, label: impl Into<String>) -> Self {
+        callsite::register("LoggedReader::new");
+        LoggedReader {
+            inner,
+            label: label.into(),
+            bytes: 0,
+            fatal: false,
+            location: Location::caller(),
+        }
+    }
+
+    /// Makes I/O errors panic rather than be returned.
+    pub fn panicking(self) -> Self {
+        LoggedReader {
+            fatal: true,
+            ..self
+        }
+    }
+
+    /// The number of bytes read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn check<T>(&self, result: io::Result<T>) -> io::Result<T> {
+        if let Err(e) = &result {
+            if e.kind() != io::ErrorKind::Interrupted {
+                io_failed(
+                    "LoggedReader::new",
+                    &format!("read `{}` failed after {} bytes", self.label, self.bytes),
+                    e,
+                    self.fatal,
+                    self.location,
+                );
+            }
+        }
+        result
+    }
+}
+
+impl<R: Read> Read for LoggedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let result = self.inner.read(buf);
+        if let Ok(n) = result {
+            self.bytes += n as u64;
+        }
+        self.check(result)
+    }
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> io::Result<()> {
+        // Reads piecewise, so that the bytes read before a failure are counted.
+        while !buf.is_empty() {
+            match self.inner.read(buf) {
+                Ok(0) => {
+                    let eof = io::Error::new(io::ErrorKind::UnexpectedEof, "failed to fill buffer");
+                    return self.check(Err(eof));
+                }
+                Ok(n) => {
+                    self.bytes += n as u64;
+                    buf = &mut buf[n..];
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return self.check(Err(e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`Write`] wrapper that logs the errors of the inner writer, with an
+/// operation label and the number of bytes written so far, to a
+/// [`tracing::Subscriber`] at a [`WARN`] level.
+///
+/// Errors are still returned, unless [`panicking`](Self::panicking) is
+/// called, to panic on errors instead, logging them at an [`ERROR`] level.
+/// Interrupted writes are retried by callers, and not logged.
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[derive(Debug)]
+pub struct LoggedWriter<W> {
+    inner: W,
+    label: String,
+    bytes: u64,
+    fatal: bool,
+    location: &'static Location<'static>,
+}
+
+impl<W> LoggedWriter<W> {
+    /// Wraps `inner`, labelling its errors with `label`. The location of this
+    /// call is reported for the errors.
+    #[track_caller]
+    pub fn new(inner: W, label: impl Into<String>) -> Self {
+        callsite::register("LoggedWriter::new");
+        LoggedWriter {
+            inner,
+            label: label.into(),
+            bytes: 0,
+            fatal: false,
+            location: Location::caller(),
+        }
+    }
+
+    /// Makes I/O errors panic rather than be returned.
+    pub fn panicking(self) -> Self {
+        LoggedWriter {
+            fatal: true,
+            ..self
+        }
+    }
+
+    /// The number of bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn check<T>(&self, operation: &str, result: io::Result<T>) -> io::Result<T> {
+        if let Err(e) = &result {
+            if e.kind() != io::ErrorKind::Interrupted {
+                io_failed(
+                    "LoggedWriter::new",
+                    &format!(
+                        "{} `{}` failed after {} bytes",
+                        operation, self.label, self.bytes
+                    ),
+                    e,
+                    self.fatal,
+                    self.location,
+                );
+            }
+        }
+        result
+    }
+}
+
+impl<W: Write> Write for LoggedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.inner.write(buf);
+        if let Ok(n) = result {
+            self.bytes += n as u64;
+        }
+        self.check("write", result)
+    }
+
+    fn write_all(&mut self, mut buf: &[u8]) -> io::Result<()> {
+        // Writes piecewise, so that the bytes written before a failure are
+        // counted.
+        while !buf.is_empty() {
+            match self.inner.write(buf) {
+                Ok(0) => {
+                    let zero =
+                        io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer");
+                    return self.check("write", Err(zero));
+                }
+                Ok(n) => {
+                    self.bytes += n as u64;
+                    buf = &buf[n..];
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return self.check("write", Err(e)),
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let result = self.inner.flush();
+        self.check("flush", result)
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn io_failed(
+    method: &'static str,
+    msg: &str,
+    error: &io::Error,
+    fatal: bool,
+    location: &'static Location<'static>,
+) {
+    let level = if fatal {
+        crate::facade::Level::ERROR
+    } else {
+        crate::facade::Level::WARN
+    };
+    let record = FailureRecord::new(method, msg, Some(error), level, fatal)
+        .with_location(location)
+        .with_io_error(error);
+    if fatal {
+        fail(&record);
+    }
+    emit(&record);
+}
@@ -0,0 +1,126 @@
+//! Extensions for looking up elements of slices and maps.
+
+use crate::{callsite, failed_with};
+use alloc::collections::BTreeMap;
+use core::borrow::Borrow;
+use core::fmt;
+#[cfg(feature = "std")]
+use {
+    core::hash::{BuildHasher, Hash},
+    std::collections::HashMap,
+};
+
+/// Extension trait for looking up elements of slices (and, through them, of
+/// [`Vec`](alloc::vec::Vec)s), [`HashMap`](std::collections::HashMap)s and
+/// [`BTreeMap`]s by an index or a key `Q`.
+pub trait LookupExt<Q> {
+    /// The type of the elements.
+    type Output: ?Sized;
+
+    /// Returns a reference to the element at `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such element, logging the missing index or key
+    /// to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn get_or_log(&self, key: Q) -> &Self::Output;
+
+    /// Returns a mutable reference to the element at `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such element, logging the missing index or key
+    /// to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn get_mut_or_log(&mut self, key: Q) -> &mut Self::Output;
+}
+
+impl<T> LookupExt<usize> for [T] {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn get_or_log(&self, index: usize) -> &T {
+        callsite::register("slice::get_or_log");
+        let len = self.len();
+        match self.get(index) {
+            Some(element) => element,
+            None => out_of_bounds(
+                "slice::get_or_log",
+                "called `slice::get_or_log()` with an index out of bounds",
+                index,
+                len,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn get_mut_or_log(&mut self, index: usize) -> &mut T {
+        callsite::register("slice::get_mut_or_log");
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(element) => element,
+            None => out_of_bounds(
+                "slice::get_mut_or_log",
+                "called `slice::get_mut_or_log()` with an index out of bounds",
+                index,
+                len,
+            ),
+        }
+    }
+}
+
+macro_rules! impl_lookup_ext {
+    ($ty:ty, $name:literal, [$($generics:tt)*] where $($bounds:tt)+) => {
+        impl<'q, Q, $($generics)*> LookupExt<&'q Q> for $ty
+        where
+            Q: fmt::Debug + ?Sized,
+            $($bounds)+
+        {
+            type Output = V;
+
+            #[inline]
+            #[track_caller]
+            fn get_or_log(&self, key: &'q Q) -> &V {
+                callsite::register(concat!($name, "::get_or_log"));
+                match self.get(key) {
+                    Some(value) => value,
+                    None => failed_with(
+                        concat!($name, "::get_or_log"),
+                        concat!("called `", $name, "::get_or_log()` with a missing key"),
+                        &key,
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn get_mut_or_log(&mut self, key: &'q Q) -> &mut V {
+                callsite::register(concat!($name, "::get_mut_or_log"));
+                match self.get_mut(key) {
+                    Some(value) => value,
+                    None => failed_with(
+                        concat!($name, "::get_mut_or_log"),
+                        concat!("called `", $name, "::get_mut_or_log()` with a missing key"),
+                        &key,
+                    ),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_lookup_ext!(HashMap<K, V, S>, "HashMap", [K, V, S] where K: Borrow<Q> + Hash + Eq, Q: Hash + Eq, S: BuildHasher);
+impl_lookup_ext!(BTreeMap<K, V>, "BTreeMap", [K, V] where K: Borrow<Q> + Ord, Q: Ord);
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn out_of_bounds(method: &'static str, msg: &str, index: usize, len: usize) -> ! {
+    failed_with(method, msg, &format_args!("index {}, len {}", index, len))
+}
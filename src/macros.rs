@@ -0,0 +1,633 @@
+//! Macro forms for checks that don't fit an extension trait.
+
+use crate::FailureRecord;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use {
+    core::panic::Location,
+    std::time::{Duration, Instant},
+};
+
+/// Asserts that an expression matches a pattern, like
+/// `assert!(matches!(expression, pattern))`.
+///
+/// # Panics
+///
+/// Panics if the value doesn't match, logging the value and the stringified
+/// pattern (as the `pattern` entry of the `unwrap.details` field) to a
+/// [`tracing::Subscriber`] at an [`ERROR`] level. An optional message replaces
+/// the default one.
+///
+/// ```
+/// use tracing_unwrap::matches_or_log;
+///
+/// let reply: Result<u16, ()> = Ok(204);
+/// matches_or_log!(reply, Ok(200..=299));
+/// matches_or_log!(reply, Ok(code) if code != 200, "expected an empty reply");
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[macro_export]
+macro_rules! matches_or_log {
+    ($expression:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {{
+        $crate::__private::register("matches_or_log!");
+        match $expression {
+            $pattern $(if $guard)? => {}
+            ref value => $crate::__private::mismatched(
+                "called `matches_or_log!()` on a non-matching value",
+                stringify!($pattern $(if $guard)?),
+                value,
+            ),
+        }
+    }};
+    ($expression:expr, $pattern:pat $(if $guard:expr)?, $msg:expr $(,)?) => {{
+        $crate::__private::register("matches_or_log!");
+        match $expression {
+            $pattern $(if $guard)? => {}
+            ref value => $crate::__private::mismatched(
+                $msg,
+                stringify!($pattern $(if $guard)?),
+                value,
+            ),
+        }
+    }};
+}
+
+/// Binds the content of an [`Ok`] to a pattern, like
+/// `let Ok(pattern) = expression else { panic!() };`.
+///
+/// # Panics
+///
+/// Panics if the value is an [`Err`], or its content doesn't match the
+/// pattern, logging the passed message (and the content of the [`Err`], if
+/// any) to a [`tracing::Subscriber`] at an [`ERROR`] level.
+///
+/// # Early return
+///
+/// With a trailing `; else <diverging expression>`, logs at a [`WARN`] level
+/// and evaluates that expression instead of panicking.
+///
+/// ```
+/// use tracing_unwrap::let_ok_or_log;
+///
+/// fn port(input: &str) -> Option<u16> {
+///     let_ok_or_log!(let Ok(port) = input.parse::<u16>(), "invalid port"; else return None);
+///     Some(port)
+/// }
+///
+/// let_ok_or_log!(let Ok((host, port)) = Ok::<_, ()>(("localhost", 80)));
+/// assert_eq!(port, 80);
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+#[macro_export]
+macro_rules! let_ok_or_log {
+    (let Ok($pattern:pat) = $expression:expr $(,)?) => {
+        $crate::let_ok_or_log!(
+            let Ok($pattern) = $expression,
+            "called `let_ok_or_log!()` on an `Err` value"
+        )
+    };
+    (let Ok($pattern:pat) = $expression:expr, $msg:expr $(,)?) => {
+        $crate::__private::register("let_ok_or_log!");
+        let value = match $expression {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(e) => $crate::__private::failed_with("let_ok_or_log!", $msg, &e),
+        };
+        #[allow(irrefutable_let_patterns)]
+        let $pattern = value else {
+            $crate::__private::failed("let_ok_or_log!", $msg)
+        };
+    };
+    (let Ok($pattern:pat) = $expression:expr, $msg:expr; else $diverge:expr) => {
+        $crate::__private::register("let_ok_or_log!");
+        let value = match $expression {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(e) => {
+                $crate::__private::discarded_with("let_ok_or_log!", $msg, &e);
+                $diverge
+            }
+        };
+        #[allow(irrefutable_let_patterns)]
+        let $pattern = value else {
+            $crate::__private::discarded("let_ok_or_log!", $msg);
+            $diverge
+        };
+    };
+}
+
+/// Binds the content of a [`Some`] to a pattern, like
+/// `let Some(pattern) = expression else { panic!() };`.
+///
+/// # Panics
+///
+/// Panics if the value is a [`None`], or its content doesn't match the
+/// pattern, logging the passed message to a [`tracing::Subscriber`] at an
+/// [`ERROR`] level.
+///
+/// # Early return
+///
+/// With a trailing `; else <diverging expression>`, logs at a [`WARN`] level
+/// and evaluates that expression instead of panicking.
+///
+/// ```
+/// use tracing_unwrap::let_some_or_log;
+///
+/// fn first_word(line: &str) -> &str {
+///     let_some_or_log!(let Some(word) = line.split_whitespace().next(), "empty line"; else return "");
+///     word
+/// }
+///
+/// let_some_or_log!(let Some(cfg) = Some("debug"), "config missing");
+/// assert_eq!(cfg, "debug");
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+#[macro_export]
+macro_rules! let_some_or_log {
+    (let Some($pattern:pat) = $expression:expr $(,)?) => {
+        $crate::let_some_or_log!(
+            let Some($pattern) = $expression,
+            "called `let_some_or_log!()` on a `None` value"
+        )
+    };
+    (let Some($pattern:pat) = $expression:expr, $msg:expr $(,)?) => {
+        $crate::__private::register("let_some_or_log!");
+        let ::core::option::Option::Some($pattern) = $expression else {
+            $crate::__private::failed("let_some_or_log!", $msg)
+        };
+    };
+    (let Some($pattern:pat) = $expression:expr, $msg:expr; else $diverge:expr) => {
+        $crate::__private::register("let_some_or_log!");
+        let ::core::option::Option::Some($pattern) = $expression else {
+            $crate::__private::discarded("let_some_or_log!", $msg);
+            $diverge
+        };
+    };
+}
+
+/// Returns early from the enclosing function if a condition doesn't hold,
+/// logging the condition's source text (also as the `condition` entry of the
+/// `unwrap.details` field) to a [`tracing::Subscriber`] at a [`WARN`] level.
+///
+/// The second argument is the value to return, if the function returns one.
+/// A leading `level: <Level>` argument changes the level of the event.
+///
+/// ```
+/// use tracing::Level;
+/// use tracing_unwrap::guard_or_log;
+///
+/// fn average(values: &[f64]) -> Option<f64> {
+///     guard_or_log!(!values.is_empty(), None);
+///     Some(values.iter().sum::<f64>() / values.len() as f64)
+/// }
+///
+/// fn apply(version: u32) {
+///     guard_or_log!(level: Level::ERROR, version >= 2);
+///     // ...
+/// }
+/// ```
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+#[macro_export]
+macro_rules! guard_or_log {
+    (level: $level:expr, $condition:expr $(,)?) => {
+        $crate::guard_or_log!(level: $level, $condition, ())
+    };
+    (level: $level:expr, $condition:expr, $value:expr $(,)?) => {
+        $crate::__private::register("guard_or_log!");
+        if !$condition {
+            $crate::__private::guard_failed($level, stringify!($condition));
+            return $value;
+        }
+    };
+    ($condition:expr $(,)?) => {
+        $crate::guard_or_log!(level: $crate::__private::Level::WARN, $condition, ())
+    };
+    ($condition:expr, $value:expr $(,)?) => {
+        $crate::guard_or_log!(level: $crate::__private::Level::WARN, $condition, $value)
+    };
+}
+
+/// Unwraps a [`Result`] or an [`Option`], like
+/// [`ResultExt::unwrap_or_log`](crate::ResultExt::unwrap_or_log) and
+/// [`OptionExt::unwrap_or_log`](crate::OptionExt::unwrap_or_log), recording
+/// the source text of the expression as the `expression` entry of the
+/// `unwrap.details` field.
+///
+/// # Panics
+///
+/// Panics if the value is an [`Err`] or a [`None`], logging the expression
+/// and the content of the [`Err`], if any, to a [`tracing::Subscriber`] at an
+/// [`ERROR`] level.
+///
+/// ```
+/// use tracing_unwrap::unwrap_or_log;
+///
+/// let port: u16 = unwrap_or_log!("8080".parse());
+/// let first = unwrap_or_log!([1, 2, 3].first());
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[macro_export]
+macro_rules! unwrap_or_log {
+    ($expression:expr $(,)?) => {{
+        $crate::__private::register("unwrap_or_log!");
+        $crate::__private::Unwrap::unwrap_expression($expression, stringify!($expression))
+    }};
+}
+
+/// Unwraps a [`Result`] or an [`Option`], like
+/// [`ResultExt::expect_or_log`](crate::ResultExt::expect_or_log) and
+/// [`OptionExt::expect_or_log`](crate::OptionExt::expect_or_log), recording
+/// the source text of the expression as the `expression` entry of the
+/// `unwrap.details` field.
+///
+/// # Panics
+///
+/// Panics if the value is an [`Err`] or a [`None`], logging the passed
+/// message, the expression and the content of the [`Err`], if any, to a
+/// [`tracing::Subscriber`] at an [`ERROR`] level.
+///
+/// ```
+/// use tracing_unwrap::expect_or_log;
+///
+/// let port: u16 = expect_or_log!("8080".parse(), "invalid port");
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[macro_export]
+macro_rules! expect_or_log {
+    ($expression:expr, $msg:expr $(,)?) => {{
+        $crate::__private::register("expect_or_log!");
+        $crate::__private::Unwrap::expect_expression($expression, $msg, stringify!($expression))
+    }};
+}
+
+/// A [`Result`] or an [`Option`], as accepted by [`unwrap_or_log!`] and
+/// [`expect_or_log!`].
+#[doc(hidden)]
+pub trait Unwrap {
+    type Output;
+
+    #[track_caller]
+    fn unwrap_expression(self, expression: &str) -> Self::Output;
+
+    #[track_caller]
+    fn expect_expression(self, msg: &str, expression: &str) -> Self::Output;
+}
+
+impl<T, E: fmt::Debug> Unwrap for Result<T, E> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_expression(self, expression: &str) -> T {
+        match self {
+            Ok(t) => t,
+            Err(e) => expression_failed(
+                "unwrap_or_log!",
+                "called `unwrap_or_log!()` on an `Err` value",
+                Some(&e),
+                expression,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_expression(self, msg: &str, expression: &str) -> T {
+        match self {
+            Ok(t) => t,
+            Err(e) => expression_failed("expect_or_log!", msg, Some(&e), expression),
+        }
+    }
+}
+
+impl<T> Unwrap for Option<T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn unwrap_expression(self, expression: &str) -> T {
+        match self {
+            Some(t) => t,
+            None => expression_failed(
+                "unwrap_or_log!",
+                "called `unwrap_or_log!()` on a `None` value",
+                None,
+                expression,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_expression(self, msg: &str, expression: &str) -> T {
+        match self {
+            Some(t) => t,
+            None => expression_failed("expect_or_log!", msg, None, expression),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn expression_failed(
+    method: &'static str,
+    msg: &str,
+    value: Option<&dyn fmt::Debug>,
+    expression: &str,
+) -> ! {
+    let record = FailureRecord::new(method, msg, value, crate::facade::Level::ERROR, true);
+    crate::fail(&record.with_expression(expression))
+}
+
+/// Unwraps several [`Result`] and [`Option`] values at once, returning their
+/// contents as a tuple.
+///
+/// # Panics
+///
+/// Panics if any of the values is an [`Err`] or a [`None`]. All the
+/// expressions are evaluated first, and every failure is logged in a single
+/// event, with the source text of each failed expression, to a
+/// [`tracing::Subscriber`] at an [`ERROR`] level.
+///
+/// ```
+/// use tracing_unwrap::unwrap_all_or_log;
+///
+/// let (width, height) = unwrap_all_or_log!("80".parse::<u16>(), "24".parse::<u16>());
+/// let (name,) = unwrap_all_or_log!(std::env::args().next());
+/// ```
+///
+/// There is no fixed limit on the number of expressions, other than the
+/// compiler's recursion limit.
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[macro_export]
+macro_rules! unwrap_all_or_log {
+    ($($expression:expr),+ $(,)?) => {{
+        $crate::__private::register("unwrap_all_or_log!");
+        let mut failures = $crate::__private::Failures::default();
+        $crate::unwrap_all_or_log!(@check failures [] $($expression),+)
+    }};
+    // Binds each value in its own expansion, so that the `value`s are distinct.
+    (@check $failures:ident [$($value:ident)*] $expression:expr $(, $rest:expr)*) => {{
+        let value = $failures.check(stringify!($expression), $expression);
+        $crate::unwrap_all_or_log!(@check $failures [$($value)* value] $($rest),*)
+    }};
+    (@check $failures:ident [$($value:ident)*]) => {{
+        $failures.finish();
+        ($($crate::__private::Failures::checked($value),)*)
+    }};
+}
+
+/// Collects the failures of [`unwrap_all_or_log!`].
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Failures {
+    failed: Vec<(&'static str, String)>,
+}
+
+impl Failures {
+    pub fn check<C: Check>(&mut self, expression: &'static str, value: C) -> Option<C::Output> {
+        match value.check() {
+            Ok(output) => Some(output),
+            Err(failure) => {
+                self.failed.push((expression, failure));
+                None
+            }
+        }
+    }
+
+    /// Unwraps a value once [`finish`](Failures::finish) has checked that
+    /// none of them failed.
+    pub fn checked<T>(value: Option<T>) -> T {
+        value.expect("checked by `Failures::finish`")
+    }
+
+    #[track_caller]
+    pub fn finish(self) {
+        if !self.failed.is_empty() {
+            crate::failed_with(
+                "unwrap_all_or_log!",
+                "called `unwrap_all_or_log!()` with failed expressions",
+                &FailureList(&self.failed),
+            );
+        }
+    }
+}
+
+struct FailureList<'a>(&'a [(&'static str, String)]);
+
+impl fmt::Debug for FailureList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, (expression, failure)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`: {}", expression, failure)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// A [`Result`] or an [`Option`], as accepted by [`unwrap_all_or_log!`].
+#[doc(hidden)]
+pub trait Check {
+    type Output;
+
+    /// Returns the content, or the `Debug` rendering of the failure.
+    fn check(self) -> Result<Self::Output, String>;
+}
+
+impl<T, E: fmt::Debug> Check for Result<T, E> {
+    type Output = T;
+
+    fn check(self) -> Result<T, String> {
+        self.map_err(|e| format!("Err({:?})", e))
+    }
+}
+
+impl<T> Check for Option<T> {
+    type Output = T;
+
+    fn check(self) -> Result<T, String> {
+        self.ok_or_else(|| "None".to_string())
+    }
+}
+
+/// Waits on several futures at once, yielding the content of the [`Ok`] of
+/// the first one to complete, like a `tokio::select!` over futures resolving
+/// to `Result<T, _>`. Must be used inside an `async` context.
+///
+/// Each branch is written as `label = future`, where `label` names the branch
+/// in the logged event. Branches are polled in the order they are written.
+///
+/// # Panics
+///
+/// Panics if the first future to complete resolves to an [`Err`], logging the
+/// branch label and the content of the [`Err`] to a [`tracing::Subscriber`]
+/// at an [`ERROR`] level. See [`try_select_or_log!`] to propagate the error
+/// instead.
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use tracing_unwrap::select_or_log;
+///
+/// let primary = async { Ok::<_, std::io::Error>("primary") };
+/// let replica = std::future::pending::<Result<&str, String>>();
+/// let reply = select_or_log! { primary = primary, replica = replica };
+/// assert_eq!(reply, "primary");
+/// # });
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[macro_export]
+macro_rules! select_or_log {
+    // Pins each future under a name of its own expansion, so that the labels
+    // are never bound and a later future can't pick up an earlier one.
+    (@pin $branch:path; [$($pinned:ident = $pinned_label:ident,)*] $label:ident = $future:expr, $($rest:tt)*) => {{
+        let mut future = ::core::pin::pin!($future);
+        $crate::select_or_log!(@pin $branch; [$($pinned = $pinned_label,)* future = $label,] $($rest)*)
+    }};
+    (@pin $branch:path; [$($pinned:ident = $pinned_label:ident,)*]) => {
+        ::core::future::poll_fn(|cx| {
+            $(
+                if let ::core::task::Poll::Ready(output) =
+                    ::core::future::Future::poll($pinned.as_mut(), cx)
+                {
+                    return ::core::task::Poll::Ready($branch(stringify!($pinned_label), output));
+                }
+            )*
+            ::core::task::Poll::Pending
+        })
+        .await
+    };
+    ($($label:ident = $future:expr),+ $(,)?) => {{
+        $crate::__private::register("select_or_log!");
+        $crate::select_or_log!(@pin $crate::__private::select_branch_or_log; [] $($label = $future,)+)
+    }};
+}
+
+/// Waits on several futures at once, yielding the [`Result`] of the first one
+/// to complete, like [`select_or_log!`], but propagating an [`Err`] instead of
+/// panicking. All branches must share the error type.
+///
+/// If the first future to complete resolves to an [`Err`], logs the branch
+/// label and the content of the [`Err`] to a [`tracing::Subscriber`] at a
+/// [`WARN`] level.
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use tracing_unwrap::try_select_or_log;
+///
+/// async fn lookup() -> Result<u32, String> {
+///     let fast = async { Err::<u32, _>("cache miss".to_string()) };
+///     let slow = std::future::pending();
+///     try_select_or_log! { cache = fast, database = slow }
+/// }
+///
+/// assert!(lookup().await.is_err());
+/// # });
+/// ```
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+#[macro_export]
+macro_rules! try_select_or_log {
+    ($($label:ident = $future:expr),+ $(,)?) => {{
+        $crate::__private::register("try_select_or_log!");
+        $crate::select_or_log!(@pin $crate::__private::select_branch_or_propagate; [] $($label = $future,)+)
+    }};
+}
+
+/// Runs a block as a named operation, logging the operation's name and the
+/// time it ran for to a [`tracing::Subscriber`] at an [`ERROR`] level if a
+/// panic escapes the block, before the unwind resumes.
+///
+/// This labels the phases of e.g. batch jobs, where the panic of a raw
+/// `unwrap()` would lose track of which phase failed. The block's value is
+/// returned, and `return`, `break` and `?` work within it as usual.
+///
+/// ```
+/// use tracing_unwrap::logged_scope;
+///
+/// let rows = logged_scope!("import users", {
+///     let input = "1,2,3";
+///     input.split(',').count()
+/// });
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! logged_scope {
+    ($name:expr, $body:block $(,)?) => {{
+        $crate::__private::register("logged_scope!");
+        let _scope = $crate::__private::Scope::enter($name);
+        $body
+    }};
+}
+
+/// Logs a panic that unwinds through a [`logged_scope!`].
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub struct Scope<'a> {
+    name: &'a str,
+    started: Instant,
+    location: &'static Location<'static>,
+    /// Whether the scope was entered during an unwind, which is then not its
+    /// own panic.
+    unwinding: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Scope<'a> {
+    #[track_caller]
+    pub fn enter(name: &'a str) -> Self {
+        Scope {
+            name,
+            started: Instant::now(),
+            location: Location::caller(),
+            unwinding: std::thread::panicking(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() && !self.unwinding {
+            unwound(self.name, self.started.elapsed(), self.location);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+#[cold]
+fn unwound(name: &str, elapsed: Duration, location: &'static Location<'static>) {
+    if !crate::filter::enabled(crate::facade::Level::ERROR, location) {
+        return;
+    }
+
+    let method = "logged_scope!";
+    crate::dispatch::with_dispatch(|| {
+        crate::facade::event!(
+            target: crate::TARGET,
+            crate::facade::Level::ERROR,
+            unwrap.callsite_id = %format_args!("{:016x}", crate::callsite::id(location, method)),
+            unwrap.method = method,
+            "operation `{}` panicked after {:?}",
+            name,
+            elapsed,
+        )
+    });
+}
@@ -0,0 +1,37 @@
+//! Extensions for nested options and results.
+
+use crate::{callsite, failed_with};
+use core::fmt;
+
+/// Extension trait for [`Option`]s of [`Result`]s, as returned by lookups
+/// that can fail, e.g. in configuration and cache code.
+pub trait OptionResultExt<T, E> {
+    /// Transposes an optional result and unwraps the inner result, yielding
+    /// [`None`] for [`None`] and the content of the [`Ok`] for `Some(Ok(_))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is `Some(Err(_))`, logging a message stating that
+    /// a value was present, and the content of the [`Err`], to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn transpose_or_log(self) -> Option<T>;
+}
+
+impl<T, E: fmt::Debug> OptionResultExt<T, E> for Option<Result<T, E>> {
+    #[inline]
+    #[track_caller]
+    fn transpose_or_log(self) -> Option<T> {
+        callsite::register("Option::transpose_or_log");
+        match self {
+            None => None,
+            Some(Ok(t)) => Some(t),
+            Some(Err(e)) => failed_with(
+                "Option::transpose_or_log",
+                "called `Option::transpose_or_log()` on a `Some(Err)` value",
+                &e,
+            ),
+        }
+    }
+}
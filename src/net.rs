@@ -0,0 +1,112 @@
+//! Extensions for connecting and binding network sockets.
+
+use crate::{callsite, fail, FailureRecord};
+use std::fmt;
+use std::io;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+
+/// Extension trait for [`TcpStream`].
+pub trait TcpStreamExt: Sized {
+    /// Opens a TCP connection to `addr`, like [`TcpStream::connect`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the connection fails, logging the address and the I/O error,
+    /// with its kind in the `unwrap.error_kind` field, to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn connect_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self;
+}
+
+impl TcpStreamExt for TcpStream {
+    #[inline]
+    #[track_caller]
+    fn connect_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self {
+        callsite::register("TcpStream::connect_or_log");
+        match TcpStream::connect(&addr) {
+            Ok(stream) => stream,
+            Err(e) => net_failed(
+                "TcpStream::connect_or_log",
+                "called `TcpStream::connect_or_log()` and connecting failed",
+                &addr,
+                &e,
+            ),
+        }
+    }
+}
+
+/// Extension trait for [`TcpListener`].
+pub trait TcpListenerExt: Sized {
+    /// Binds a TCP listener to `addr`, like [`TcpListener::bind`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if binding fails, logging the address and the I/O error, with
+    /// its kind in the `unwrap.error_kind` field, to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn bind_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self;
+}
+
+impl TcpListenerExt for TcpListener {
+    #[inline]
+    #[track_caller]
+    fn bind_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self {
+        callsite::register("TcpListener::bind_or_log");
+        match TcpListener::bind(&addr) {
+            Ok(listener) => listener,
+            Err(e) => net_failed(
+                "TcpListener::bind_or_log",
+                "called `TcpListener::bind_or_log()` and binding failed",
+                &addr,
+                &e,
+            ),
+        }
+    }
+}
+
+/// Extension trait for [`UdpSocket`].
+pub trait UdpSocketExt: Sized {
+    /// Binds a UDP socket to `addr`, like [`UdpSocket::bind`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if binding fails, logging the address and the I/O error, with
+    /// its kind in the `unwrap.error_kind` field, to a
+    /// [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn bind_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self;
+}
+
+impl UdpSocketExt for UdpSocket {
+    #[inline]
+    #[track_caller]
+    fn bind_or_log<A: ToSocketAddrs + fmt::Debug>(addr: A) -> Self {
+        callsite::register("UdpSocket::bind_or_log");
+        match UdpSocket::bind(&addr) {
+            Ok(socket) => socket,
+            Err(e) => net_failed(
+                "UdpSocket::bind_or_log",
+                "called `UdpSocket::bind_or_log()` and binding failed",
+                &addr,
+                &e,
+            ),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn net_failed(method: &'static str, msg: &str, addr: &dyn fmt::Debug, error: &io::Error) -> ! {
+    let address = format!("{:?}", addr);
+    let value = format_args!("address {}, {}", address, error);
+    fail(
+        &FailureRecord::new(method, msg, Some(&value), crate::facade::Level::ERROR, true)
+            .with_address(&address)
+            .with_io_error(error),
+    )
+}
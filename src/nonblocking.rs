@@ -0,0 +1,105 @@
+//! Extensions for [`nb::Result`], as returned by `embedded-hal` drivers.
+
+use crate::{callsite, failed, failed_with};
+use core::fmt;
+
+/// Extension trait for [`nb::Result`] types.
+pub trait NbResultExt<T, E> {
+    /// Unwraps a non-blocking result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`nb::Error::WouldBlock`] or an
+    /// [`nb::Error::Other`], logging a message that tells the two apart (and
+    /// the driver error, if any) to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn unwrap_or_log(self) -> T
+    where
+        E: fmt::Debug;
+
+    /// Unwraps a non-blocking result, yielding the content of an [`Ok`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is an [`nb::Error::WouldBlock`] or an
+    /// [`nb::Error::Other`], logging the passed message (and the driver error,
+    /// if any) to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn expect_or_log(self, msg: &str) -> T
+    where
+        E: fmt::Debug;
+}
+
+impl<T, E> NbResultExt<T, E> for nb::Result<T, E> {
+    #[inline]
+    #[track_caller]
+    fn unwrap_or_log(self) -> T
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("nb::Result::unwrap_or_log");
+        match self {
+            Ok(t) => t,
+            Err(nb::Error::WouldBlock) => failed(
+                "nb::Result::unwrap_or_log",
+                "called `nb::Result::unwrap_or_log()` on a `WouldBlock` value",
+            ),
+            Err(nb::Error::Other(e)) => failed_with(
+                "nb::Result::unwrap_or_log",
+                "called `nb::Result::unwrap_or_log()` on an `Other` value",
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn expect_or_log(self, msg: &str) -> T
+    where
+        E: fmt::Debug,
+    {
+        callsite::register("nb::Result::expect_or_log");
+        match self {
+            Ok(t) => t,
+            Err(nb::Error::WouldBlock) => failed_with(
+                "nb::Result::expect_or_log",
+                msg,
+                &nb::Error::<()>::WouldBlock,
+            ),
+            Err(nb::Error::Other(e)) => failed_with("nb::Result::expect_or_log", msg, &e),
+        }
+    }
+}
+
+/// Repeatedly calls `f` until it stops returning [`nb::Error::WouldBlock`],
+/// yielding the content of an [`Ok`]. This is the logging counterpart of
+/// [`nb::block!`].
+///
+/// # Panics
+///
+/// Panics if `f` returns an [`nb::Error::Other`], logging the driver error to
+/// a [`tracing::Subscriber`] at an [`ERROR`] level.
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[track_caller]
+pub fn block_or_log<T, E, F>(mut f: F) -> T
+where
+    E: fmt::Debug,
+    F: FnMut() -> nb::Result<T, E>,
+{
+    callsite::register("block_or_log");
+    loop {
+        match f() {
+            Ok(t) => return t,
+            Err(nb::Error::WouldBlock) => core::hint::spin_loop(),
+            Err(nb::Error::Other(e)) => failed_with(
+                "block_or_log",
+                "called `block_or_log()` on an `Other` value",
+                &e,
+            ),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Integration with OpenTelemetry's context propagation.
+
+use opentelemetry::baggage::BaggageExt;
+use opentelemetry::Context;
+
+/// Renders the baggage of the current OpenTelemetry context, such as a tenant
+/// or a request id propagated from upstream services, in the W3C `baggage`
+/// header format, if there is any.
+///
+/// Tracing fields must be named at compile time, so the entries are recorded
+/// together as the `unwrap.baggage` field rather than as a field each.
+pub(crate) fn baggage() -> Option<String> {
+    let context = Context::current();
+    let baggage = context.baggage();
+    (!baggage.is_empty()).then(|| baggage.to_string())
+}
@@ -0,0 +1,124 @@
+//! Panic hooks that avoid reporting failed unwraps twice.
+
+use std::cell::Cell;
+use std::panic::{self, PanicHookInfo};
+use std::thread;
+
+thread_local! {
+    /// Set when a fatal failure has been logged and is about to panic.
+    static LOGGED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a panic hook that shortens the output of panics from failed
+/// unwraps to a single line, since the failure was already logged, and defers
+/// to the previously installed hook for every other panic.
+///
+/// Failures that were filtered out, and so never logged, still go through the
+/// previous hook.
+///
+/// ```
+/// tracing_unwrap::install_quiet_panic_hook();
+/// ```
+pub fn install_quiet_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        if !LOGGED.with(|logged| logged.replace(false)) {
+            return previous(info);
+        }
+
+        let thread = thread::current();
+        let name = thread.name().unwrap_or("<unnamed>");
+        match info.location() {
+            Some(location) => {
+                eprintln!("thread '{}' panicked at {}: failure logged", name, location)
+            }
+            None => eprintln!("thread '{}' panicked: failure logged", name),
+        }
+    }));
+}
+
+/// Marks the panic that is about to happen on this thread as having been
+/// logged.
+pub(crate) fn mark_logged() {
+    LOGGED.with(|logged| logged.set(true));
+}
+
+/// Installs a panic hook that emits every panic, such as that of a plain
+/// `unwrap()` in a dependency, as an [`ERROR`] event with the panic message,
+/// the thread and the location, replacing the previous hook.
+///
+/// With the **`backtrace`** feature, the event also records a backtrace as
+/// the `unwrap.stacktrace` field. Panics from failed unwraps, which were
+/// already logged, don't emit a second event.
+///
+/// ```
+/// tracing_unwrap::init_panic_hook();
+/// ```
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[cfg(feature = "panic-hook")]
+pub fn init_panic_hook() {
+    panic::set_hook(Box::new(|info: &PanicHookInfo<'_>| traced(info, None)));
+}
+
+/// Installs a panic hook that emits every panic as an event, like
+/// [`init_panic_hook`], then runs the previously installed hook, e.g. to
+/// still print panics to stderr.
+#[cfg(feature = "panic-hook")]
+pub fn init_panic_hook_chained() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        traced(info, Some(&previous))
+    }));
+}
+
+#[cfg(feature = "panic-hook")]
+fn traced(info: &PanicHookInfo<'_>, previous: Option<&(dyn Fn(&PanicHookInfo<'_>) + Send + Sync)>) {
+    if !LOGGED.with(Cell::get) {
+        log_panic(info);
+    }
+    if let Some(previous) = previous {
+        previous(info);
+    }
+    LOGGED.with(|logged| logged.set(false));
+}
+
+#[cfg(feature = "panic-hook")]
+fn log_panic(info: &PanicHookInfo<'_>) {
+    use crate::facade::Level;
+
+    let location = info.location();
+    if location.is_some_and(|location| !crate::filter::enabled(Level::ERROR, location)) {
+        return;
+    }
+
+    let thread = thread::current();
+    let name = thread.name().unwrap_or("<unnamed>");
+    let payload = info.payload();
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => *message,
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.as_str(),
+            None => "Box<dyn Any>",
+        },
+    };
+    #[cfg(feature = "backtrace")]
+    let stacktrace = crate::stacktrace::capture();
+    #[cfg(not(feature = "backtrace"))]
+    let stacktrace: Option<String> = None;
+
+    crate::dispatch::with_dispatch(|| {
+        crate::facade::event!(
+            target: crate::TARGET,
+            Level::ERROR,
+            unwrap.filepath = location.map(|l| l.file()),
+            unwrap.lineno = location.map(|l| l.line()),
+            unwrap.columnno = location.map(|l| l.column()),
+            unwrap.thread = name,
+            unwrap.stacktrace = stacktrace.as_deref(),
+            "thread '{}' panicked: {}",
+            name,
+            message,
+        )
+    });
+}
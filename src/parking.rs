@@ -0,0 +1,219 @@
+//! Extensions for the locks of the `parking_lot` crate.
+//!
+//! These locks can't be poisoned, so lock attempts only fail by contention,
+//! which is logged with the lock's label, how long the attempt waited and
+//! what held the lock.
+
+use crate::{callsite, discarded_with, recovery};
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::panic::Location;
+use std::time::{Duration, Instant};
+
+/// Extension trait for [`parking_lot::Mutex`].
+pub trait ParkingLotMutexExt<T: ?Sized> {
+    /// Attempts to lock the mutex without blocking, like
+    /// [`Mutex::try_lock`], logging a failed attempt, with the lock's
+    /// `label`, to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_lock_or_log(&self, label: &str) -> Option<MutexGuard<'_, T>>;
+
+    /// Attempts to lock the mutex, blocking for at most `timeout`, like
+    /// [`Mutex::try_lock_for`], logging a failed attempt, with the lock's
+    /// `label` and how long it waited, to a [`tracing::Subscriber`] at a
+    /// [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_lock_for_or_log(&self, label: &str, timeout: Duration) -> Option<MutexGuard<'_, T>>;
+}
+
+impl<T: ?Sized> ParkingLotMutexExt<T> for Mutex<T> {
+    #[inline]
+    #[track_caller]
+    fn try_lock_or_log(&self, label: &str) -> Option<MutexGuard<'_, T>> {
+        callsite::register("parking_lot::Mutex::try_lock_or_log");
+        let guard = self.try_lock();
+        match guard {
+            Some(_) => {
+                recovery::succeeded("parking_lot::Mutex::try_lock_or_log", Location::caller())
+            }
+            None => contended(
+                "parking_lot::Mutex::try_lock_or_log",
+                label,
+                None,
+                mutex_holder(self),
+            ),
+        }
+        guard
+    }
+
+    #[inline]
+    #[track_caller]
+    fn try_lock_for_or_log(&self, label: &str, timeout: Duration) -> Option<MutexGuard<'_, T>> {
+        callsite::register("parking_lot::Mutex::try_lock_for_or_log");
+        let started = Instant::now();
+        let guard = self.try_lock_for(timeout);
+        match guard {
+            Some(_) => recovery::succeeded(
+                "parking_lot::Mutex::try_lock_for_or_log",
+                Location::caller(),
+            ),
+            None => contended(
+                "parking_lot::Mutex::try_lock_for_or_log",
+                label,
+                Some((timeout, started.elapsed())),
+                mutex_holder(self),
+            ),
+        }
+        guard
+    }
+}
+
+/// Extension trait for [`parking_lot::RwLock`].
+pub trait ParkingLotRwLockExt<T: ?Sized> {
+    /// Attempts to acquire shared read access without blocking, like
+    /// [`RwLock::try_read`], logging a failed attempt, with the lock's
+    /// `label`, to a [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_read_or_log(&self, label: &str) -> Option<RwLockReadGuard<'_, T>>;
+
+    /// Attempts to acquire shared read access, blocking for at most
+    /// `timeout`, like [`RwLock::try_read_for`], logging a failed attempt,
+    /// with the lock's `label` and how long it waited, to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_read_for_or_log(&self, label: &str, timeout: Duration)
+        -> Option<RwLockReadGuard<'_, T>>;
+
+    /// Attempts to acquire exclusive write access without blocking, like
+    /// [`RwLock::try_write`], logging a failed attempt, with the lock's
+    /// `label` and whether it was held for reading or writing, to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_write_or_log(&self, label: &str) -> Option<RwLockWriteGuard<'_, T>>;
+
+    /// Attempts to acquire exclusive write access, blocking for at most
+    /// `timeout`, like [`RwLock::try_write_for`], logging a failed attempt,
+    /// with the lock's `label`, how long it waited and whether it was held
+    /// for reading or writing, to a [`tracing::Subscriber`] at a [`WARN`]
+    /// level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_write_for_or_log(
+        &self,
+        label: &str,
+        timeout: Duration,
+    ) -> Option<RwLockWriteGuard<'_, T>>;
+}
+
+macro_rules! impl_rwlock_attempt {
+    ($name:ident, $method:literal, $guard:ident, $try:ident) => {
+        #[inline]
+        #[track_caller]
+        fn $name(&self, label: &str) -> Option<$guard<'_, T>> {
+            callsite::register($method);
+            let guard = self.$try();
+            match guard {
+                Some(_) => recovery::succeeded($method, Location::caller()),
+                None => contended($method, label, None, rwlock_holder(self)),
+            }
+            guard
+        }
+    };
+    ($name:ident, $method:literal, $guard:ident, $try:ident, timeout) => {
+        #[inline]
+        #[track_caller]
+        fn $name(&self, label: &str, timeout: Duration) -> Option<$guard<'_, T>> {
+            callsite::register($method);
+            let started = Instant::now();
+            let guard = self.$try(timeout);
+            match guard {
+                Some(_) => recovery::succeeded($method, Location::caller()),
+                None => contended(
+                    $method,
+                    label,
+                    Some((timeout, started.elapsed())),
+                    rwlock_holder(self),
+                ),
+            }
+            guard
+        }
+    };
+}
+
+impl<T: ?Sized> ParkingLotRwLockExt<T> for RwLock<T> {
+    impl_rwlock_attempt!(
+        try_read_or_log,
+        "parking_lot::RwLock::try_read_or_log",
+        RwLockReadGuard,
+        try_read
+    );
+    impl_rwlock_attempt!(
+        try_read_for_or_log,
+        "parking_lot::RwLock::try_read_for_or_log",
+        RwLockReadGuard,
+        try_read_for,
+        timeout
+    );
+    impl_rwlock_attempt!(
+        try_write_or_log,
+        "parking_lot::RwLock::try_write_or_log",
+        RwLockWriteGuard,
+        try_write
+    );
+    impl_rwlock_attempt!(
+        try_write_for_or_log,
+        "parking_lot::RwLock::try_write_for_or_log",
+        RwLockWriteGuard,
+        try_write_for,
+        timeout
+    );
+}
+
+/// Describes what held a mutex, as observed right after a failed attempt.
+fn mutex_holder<T: ?Sized>(mutex: &Mutex<T>) -> &'static str {
+    if mutex.is_locked() {
+        "held elsewhere"
+    } else {
+        "released since"
+    }
+}
+
+/// Describes what held a lock, as observed right after a failed attempt.
+fn rwlock_holder<T: ?Sized>(lock: &RwLock<T>) -> &'static str {
+    if lock.is_locked_exclusive() {
+        "held for writing"
+    } else if lock.is_locked() {
+        "held for reading"
+    } else {
+        "released since"
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn contended(
+    method: &'static str,
+    label: &str,
+    waited: Option<(Duration, Duration)>,
+    holder: &str,
+) {
+    match waited {
+        Some((timeout, elapsed)) => {
+            let msg = format!("lock `{}` not acquired within {:?}", label, timeout);
+            discarded_with(
+                method,
+                &msg,
+                &format_args!("{}, waited {:?}", holder, elapsed),
+            );
+        }
+        None => {
+            let msg = format!("lock `{}` not acquired", label);
+            discarded_with(method, &msg, &format_args!("{}", holder));
+        }
+    }
+}
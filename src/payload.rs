@@ -0,0 +1,81 @@
+//! A structured panic payload for failed unwraps.
+
+use crate::facade::Level;
+use crate::FailureRecord;
+use std::fmt;
+use std::panic::Location;
+
+/// The details of a failed unwrap, in a form that outlives the failure.
+///
+/// With the **`panic-payload`** feature, failed unwraps panic with this as the
+/// payload, so that custom panic hooks and supervisors can downcast it and
+/// read the details instead of parsing a panic message:
+///
+/// ```
+/// # use tracing_unwrap::UnwrapFailure;
+/// std::panic::set_hook(Box::new(|info| {
+///     if let Some(failure) = info.payload().downcast_ref::<UnwrapFailure>() {
+///         eprintln!("{} failed at {}", failure.method(), failure.location());
+///     }
+/// }));
+/// ```
+///
+/// Its [`Display`](fmt::Display) implementation renders the same message as a
+/// [`FailureRecord`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnwrapFailure {
+    method: &'static str,
+    message: String,
+    value: Option<String>,
+    level: Level,
+    location: &'static Location<'static>,
+}
+
+impl UnwrapFailure {
+    /// The method or macro that failed.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The failure message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The `Debug` rendering of the value that caused the failure, if there
+    /// is one.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// The level the failure was logged at.
+    pub fn level(&self) -> tracing::Level {
+        self.level
+    }
+
+    /// The location of the failed call.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl From<&FailureRecord<'_>> for UnwrapFailure {
+    fn from(record: &FailureRecord<'_>) -> Self {
+        UnwrapFailure {
+            method: record.method(),
+            message: record.message().to_owned(),
+            value: record.value().map(|value| format!("{:?}", value)),
+            level: record.level(),
+            location: record.location(),
+        }
+    }
+}
+
+impl fmt::Display for UnwrapFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}: {}", self.message, value),
+            None => f.write_str(&self.message),
+        }
+    }
+}
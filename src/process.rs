@@ -0,0 +1,70 @@
+//! Extensions for child processes.
+
+use crate::{callsite, fail, FailureRecord};
+use std::io;
+use std::process::{Child, ExitStatus};
+
+/// Extension trait for [`Child`] processes.
+pub trait ChildExt {
+    /// Waits for the child to exit, yielding its exit status.
+    ///
+    /// # Panics
+    ///
+    /// Panics if waiting fails, logging the child's PID and the I/O error
+    /// kind to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn wait_or_log(&mut self) -> ExitStatus;
+
+    /// Kills the child.
+    ///
+    /// # Panics
+    ///
+    /// Panics if killing fails, logging the child's PID and the I/O error
+    /// kind to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn kill_or_log(&mut self);
+}
+
+impl ChildExt for Child {
+    #[inline]
+    #[track_caller]
+    fn wait_or_log(&mut self) -> ExitStatus {
+        callsite::register("Child::wait_or_log");
+        match self.wait() {
+            Ok(status) => status,
+            Err(e) => process_failed(
+                "Child::wait_or_log",
+                "called `Child::wait_or_log()` and waiting failed",
+                self.id(),
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn kill_or_log(&mut self) {
+        callsite::register("Child::kill_or_log");
+        if let Err(e) = self.kill() {
+            process_failed(
+                "Child::kill_or_log",
+                "called `Child::kill_or_log()` and killing failed",
+                self.id(),
+                &e,
+            );
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn process_failed(method: &'static str, msg: &str, pid: u32, error: &io::Error) -> ! {
+    let value = format_args!("pid {}, error kind {:?}: {}", pid, error.kind(), error);
+    fail(
+        &FailureRecord::new(method, msg, Some(&value), crate::facade::Level::ERROR, true)
+            .with_io_error(error),
+    )
+}
@@ -0,0 +1,108 @@
+//! Defaults that depend on the build profile, i.e. on whether debug
+//! assertions are enabled.
+
+use crate::facade::Level;
+use crate::FailureRecord;
+use std::fmt;
+use std::sync::{PoisonError, RwLock};
+
+static DEBUG: RwLock<Option<ProfileDefaults>> = RwLock::new(None);
+static RELEASE: RwLock<Option<ProfileDefaults>> = RwLock::new(None);
+
+/// How failures are logged in one build profile, as installed with
+/// [`set_debug_defaults`] or [`set_release_defaults`].
+///
+/// ```
+/// use tracing::Level;
+/// use tracing_unwrap::ProfileDefaults;
+///
+/// // Chatty in development, conservative in production.
+/// tracing_unwrap::set_debug_defaults(Some(ProfileDefaults::new().with_level(Level::ERROR)));
+/// tracing_unwrap::set_release_defaults(Some(
+///     ProfileDefaults::new().with_level(Level::WARN).redacted(),
+/// ));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProfileDefaults {
+    level: Option<Level>,
+    redacted: bool,
+}
+
+impl ProfileDefaults {
+    /// Creates defaults that leave failures as they are.
+    pub fn new() -> Self {
+        ProfileDefaults::default()
+    }
+
+    /// Logs failures at `level` rather than at the `ERROR` level their
+    /// methods default to.
+    ///
+    /// Levels chosen at the call site, such as with
+    /// [`unwrap_or_log_at`](crate::ResultExt::unwrap_or_log_at) or by an
+    /// error implementing [`LevelFor`](crate::LevelFor), are kept, as is the
+    /// `WARN` level of methods that discard an error, such as
+    /// [`ok_or_log`](crate::ResultExt::ok_or_log). An
+    /// [`Escalation`](crate::Escalation) policy still takes precedence.
+    pub fn with_level(self, level: tracing::Level) -> Self {
+        ProfileDefaults {
+            level: Some(level),
+            ..self
+        }
+    }
+
+    /// Replaces the values of failures, such as the content of an [`Err`],
+    /// with `<redacted>` in events and panic messages. The `error` field and
+    /// the `unwrap.sources` of an error are left out.
+    pub fn redacted(self) -> Self {
+        ProfileDefaults {
+            redacted: true,
+            ..self
+        }
+    }
+}
+
+/// Installs the defaults for builds with debug assertions, such as the `dev`
+/// and `test` profiles. Pass `None` to leave failures as they are.
+pub fn set_debug_defaults(defaults: Option<ProfileDefaults>) {
+    *DEBUG.write().unwrap_or_else(PoisonError::into_inner) = defaults;
+}
+
+/// Installs the defaults for builds without debug assertions, such as the
+/// `release` profile. Pass `None` to leave failures as they are.
+pub fn set_release_defaults(defaults: Option<ProfileDefaults>) {
+    *RELEASE.write().unwrap_or_else(PoisonError::into_inner) = defaults;
+}
+
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// Applies the defaults of the current build profile, if any, to `record`.
+pub(crate) fn apply<'a>(record: &FailureRecord<'a>) -> FailureRecord<'a> {
+    let defaults = if cfg!(debug_assertions) {
+        &DEBUG
+    } else {
+        &RELEASE
+    };
+    let Some(defaults) = *defaults.read().unwrap_or_else(PoisonError::into_inner) else {
+        return *record;
+    };
+
+    let mut record = *record;
+    if let Some(level) = defaults.level {
+        if record.level() == Level::ERROR && !record.has_caller_level() {
+            record = record.with_level(level);
+        }
+    }
+    if defaults.redacted {
+        if record.value().is_some() {
+            record = record.with_value(&Redacted);
+        }
+        record = record.without_error();
+    }
+    record
+}
@@ -0,0 +1,30 @@
+//! Context that errors provide themselves, through the nightly
+//! `Error::provide` API.
+
+use crate::sources;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+
+/// Returns the backtrace that `error`, or the first of its sources to have
+/// one, captured when it was created, so that it needn't be captured again.
+///
+/// Only the sources up to the configured maximum depth are searched, so that
+/// a cyclic chain can't loop forever.
+pub(crate) fn backtrace(error: &dyn Error) -> Option<String> {
+    let mut error = Some(error);
+    let max_depth = sources::max_depth();
+    let mut depth = 0;
+    while let Some(current) = error {
+        if depth > max_depth {
+            break;
+        }
+        if let Some(backtrace) = std::error::request_ref::<Backtrace>(current) {
+            if backtrace.status() == BacktraceStatus::Captured {
+                return Some(backtrace.to_string());
+            }
+        }
+        error = current.source();
+        depth += 1;
+    }
+    None
+}
@@ -0,0 +1,211 @@
+//! Limiting how often a callsite that keeps discarding errors is logged.
+
+use crate::facade::Level;
+use crate::{clock, filter, reporter, FailureRecord};
+use std::collections::HashMap;
+use std::panic::Location;
+use std::sync::{Mutex, Once, PoisonError, RwLock};
+use std::thread;
+use std::time::Duration;
+
+static RATE_LIMIT: RwLock<Option<RateLimit>> = RwLock::new(None);
+
+/// The current window of each callsite that has discarded an error.
+static WINDOWS: Mutex<Option<HashMap<&'static Location<'static>, Window>>> = Mutex::new(None);
+
+/// Starts the thread that reports the windows that close once failures stop.
+static SWEEPER: Once = Once::new();
+
+/// The longest the sweeper thread sleeps, so that it picks up a shorter
+/// window soon after one is installed.
+const MAX_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The shortest the sweeper thread sleeps, even for a tiny window.
+const MIN_SWEEP_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A policy that logs at most a few discarded errors per callsite within a
+/// time window, to keep e.g. an `ok_or_log()` in a hot loop from flooding
+/// the logs during an outage.
+///
+/// When a window closes in which errors were suppressed, a summary event
+/// records how many there were as the `suppressed` entry of the
+/// `unwrap.details` field, even if the callsite has stopped failing. Fatal
+/// failures are never suppressed.
+///
+/// ```
+/// use std::time::Duration;
+/// use tracing_unwrap::RateLimit;
+///
+/// // At most 5 events per callsite every 10 seconds.
+/// let limit = RateLimit::new(Duration::from_secs(10)).with_burst(5);
+/// tracing_unwrap::set_rate_limit(Some(limit));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    window: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Creates a policy that logs one discarded error per callsite within
+    /// each `window`.
+    pub fn new(window: Duration) -> Self {
+        RateLimit { window, burst: 1 }
+    }
+
+    /// Logs up to `burst` discarded errors per callsite within each window,
+    /// rather than one.
+    pub fn with_burst(self, burst: u32) -> Self {
+        RateLimit {
+            burst: burst.max(1),
+            ..self
+        }
+    }
+}
+
+struct Window {
+    started: Duration,
+    logged: u32,
+    suppressed: u64,
+    /// The method and level of the last suppressed failure, for the summary.
+    method: &'static str,
+    level: Level,
+}
+
+/// The failures suppressed at a callsite within a window that closed.
+struct Summary {
+    location: &'static Location<'static>,
+    method: &'static str,
+    level: Level,
+    suppressed: u64,
+}
+
+/// Installs a process-wide [`RateLimit`] for discarded errors, replacing any
+/// previous one. Pass `None` to log every discarded error.
+///
+/// Failures suppressed under the previous policy are summarized right away.
+/// The time is measured with the configured [`Clock`](crate::Clock).
+pub fn set_rate_limit(limit: Option<RateLimit>) {
+    *RATE_LIMIT.write().unwrap_or_else(PoisonError::into_inner) = limit;
+    let windows = WINDOWS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let summaries = windows
+        .into_iter()
+        .flatten()
+        .filter_map(|(location, window)| summarize(location, &window));
+    report(summaries.collect());
+}
+
+fn current() -> Option<RateLimit> {
+    *RATE_LIMIT.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns whether `record` is to be logged, counting it as suppressed at its
+/// callsite otherwise.
+pub(crate) fn admit(record: &FailureRecord<'_>) -> bool {
+    if record.is_fatal() {
+        return true;
+    }
+    let Some(limit) = current() else {
+        return true;
+    };
+
+    let now = clock::now();
+    let (admitted, summaries) = {
+        let mut windows = WINDOWS.lock().unwrap_or_else(PoisonError::into_inner);
+        let windows = windows.get_or_insert_with(HashMap::new);
+        let summaries = close(windows, limit, now);
+        let window = windows.entry(record.location()).or_insert(Window {
+            started: now,
+            logged: 0,
+            suppressed: 0,
+            method: record.method(),
+            level: record.level(),
+        });
+
+        let admitted = window.logged < limit.burst;
+        if admitted {
+            window.logged += 1;
+        } else {
+            window.suppressed += 1;
+            window.method = record.method();
+            window.level = record.level();
+        }
+        (admitted, summaries)
+    };
+
+    report(summaries);
+    if !admitted {
+        SWEEPER.call_once(spawn_sweeper);
+    }
+    admitted
+}
+
+/// Removes the windows that have closed by `now`, returning the summaries of
+/// those in which failures were suppressed.
+fn close(
+    windows: &mut HashMap<&'static Location<'static>, Window>,
+    limit: RateLimit,
+    now: Duration,
+) -> Vec<Summary> {
+    let mut summaries = Vec::new();
+    windows.retain(|location, window| {
+        if now.saturating_sub(window.started) < limit.window {
+            return true;
+        }
+        summaries.extend(summarize(location, window));
+        false
+    });
+    summaries
+}
+
+fn summarize(location: &'static Location<'static>, window: &Window) -> Option<Summary> {
+    (window.suppressed > 0).then_some(Summary {
+        location,
+        method: window.method,
+        level: window.level,
+        suppressed: window.suppressed,
+    })
+}
+
+/// Reports a summary event for each window that the filter enables, outside
+/// of the lock on the windows.
+fn report(summaries: Vec<Summary>) {
+    for summary in summaries {
+        let msg = format!(
+            "suppressed {} more failures of `{}` at {}",
+            summary.suppressed, summary.method, summary.location
+        );
+        let record = FailureRecord::new(summary.method, &msg, None, summary.level, false)
+            .with_location(summary.location)
+            .with_suppressed(summary.suppressed);
+        if filter::enabled(record.level(), record.location()) {
+            reporter::report(&record);
+        }
+    }
+}
+
+/// Starts a thread that periodically reports the windows that have closed,
+/// so that suppressed failures are summarized once a callsite stops failing.
+fn spawn_sweeper() {
+    let spawned = thread::Builder::new()
+        .name("tracing-unwrap-rate-limit".into())
+        .spawn(|| loop {
+            let interval = current().map_or(MAX_SWEEP_INTERVAL, |limit| {
+                limit.window.clamp(MIN_SWEEP_INTERVAL, MAX_SWEEP_INTERVAL)
+            });
+            thread::sleep(interval);
+
+            let Some(limit) = current() else { continue };
+            let summaries = match &mut *WINDOWS.lock().unwrap_or_else(PoisonError::into_inner) {
+                Some(windows) => close(windows, limit, clock::now()),
+                None => continue,
+            };
+            report(summaries);
+        });
+    if let Err(e) = spawned {
+        crate::facade::warn!(target: crate::TARGET, "failed to spawn the rate limit thread: {}", e);
+    }
+}
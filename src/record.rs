@@ -0,0 +1,417 @@
+//! The description of a failure handed to formatters and hooks.
+
+use crate::callsite;
+use crate::facade::Level;
+use core::error::Error;
+use core::fmt;
+use core::panic::Location;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Describes a failed unwrap or a discarded error, as it is about to be
+/// logged.
+///
+/// Its [`Display`](fmt::Display) implementation renders the default event
+/// message: the failure message, followed by the offending value if there is
+/// one.
+#[derive(Clone, Copy)]
+pub struct FailureRecord<'a> {
+    method: &'static str,
+    message: &'a str,
+    value: Option<&'a dyn fmt::Debug>,
+    level: Level,
+    caller_level: bool,
+    location: &'static Location<'static>,
+    fatal: bool,
+    during_unwind: bool,
+    pattern: Option<&'a str>,
+    condition: Option<&'a str>,
+    expression: Option<&'a str>,
+    error: Option<&'a (dyn Error + 'static)>,
+    error_type: Option<&'static str>,
+    attempt: Option<u32>,
+    retry_delay: Option<Duration>,
+    address: Option<&'a str>,
+    #[cfg(feature = "std")]
+    error_kind: Option<io::ErrorKind>,
+    occurrence: Option<u64>,
+    suppressed: Option<u64>,
+    /// A backtrace captured before the record was logged, e.g. when it was
+    /// buffered, to log in place of a fresh one.
+    stacktrace: Option<&'a str>,
+}
+
+// Without the standard library, most of the extensions that set the details
+// of a record are unavailable.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl<'a> FailureRecord<'a> {
+    #[track_caller]
+    pub(crate) fn new(
+        method: &'static str,
+        message: &'a str,
+        value: Option<&'a dyn fmt::Debug>,
+        level: Level,
+        fatal: bool,
+    ) -> Self {
+        FailureRecord {
+            method,
+            message,
+            value,
+            level,
+            caller_level: false,
+            location: Location::caller(),
+            fatal,
+            #[cfg(feature = "std")]
+            during_unwind: std::thread::panicking(),
+            #[cfg(not(feature = "std"))]
+            during_unwind: false,
+            pattern: None,
+            condition: None,
+            expression: None,
+            error: None,
+            error_type: None,
+            attempt: None,
+            retry_delay: None,
+            address: None,
+            #[cfg(feature = "std")]
+            error_kind: None,
+            occurrence: None,
+            suppressed: None,
+            stacktrace: None,
+        }
+    }
+
+    pub(crate) fn with_level(self, level: Level) -> Self {
+        FailureRecord { level, ..self }
+    }
+
+    /// Marks the level as chosen at the call site, e.g. with
+    /// `unwrap_or_log_at`, so that profile defaults don't replace it.
+    pub(crate) fn with_caller_level(self) -> Self {
+        FailureRecord {
+            caller_level: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn has_caller_level(&self) -> bool {
+        self.caller_level
+    }
+
+    pub(crate) fn with_value(self, value: &'a dyn fmt::Debug) -> Self {
+        FailureRecord {
+            value: Some(value),
+            ..self
+        }
+    }
+
+    /// Replaces the caller's location, for failures detected away from the
+    /// call that set them up, such as in a future.
+    pub(crate) fn with_location(self, location: &'static Location<'static>) -> Self {
+        FailureRecord { location, ..self }
+    }
+
+    pub(crate) fn with_pattern(self, pattern: &'a str) -> Self {
+        FailureRecord {
+            pattern: Some(pattern),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_error(self, error: &'a (dyn Error + 'static)) -> Self {
+        FailureRecord {
+            error: Some(error),
+            ..self
+        }
+    }
+
+    /// Drops the error that caused the failure, so that neither it nor its
+    /// sources are logged.
+    pub(crate) fn without_error(self) -> Self {
+        FailureRecord {
+            error: None,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_error_type(self, error_type: &'static str) -> Self {
+        FailureRecord {
+            error_type: Some(error_type),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_attempt(self, attempt: u32, retry_delay: Option<Duration>) -> Self {
+        FailureRecord {
+            attempt: Some(attempt),
+            retry_delay,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn with_error_kind(self, error_kind: io::ErrorKind) -> Self {
+        FailureRecord {
+            error_kind: Some(error_kind),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_during_unwind(self, during_unwind: bool) -> Self {
+        FailureRecord {
+            during_unwind,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn with_io_error(self, error: &'a io::Error) -> Self {
+        self.with_error(error).with_error_kind(error.kind())
+    }
+
+    pub(crate) fn with_address(self, address: &'a str) -> Self {
+        FailureRecord {
+            address: Some(address),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_occurrence(self, occurrence: u64) -> Self {
+        FailureRecord {
+            occurrence: Some(occurrence),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_suppressed(self, suppressed: u64) -> Self {
+        FailureRecord {
+            suppressed: Some(suppressed),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_condition(self, condition: &'a str) -> Self {
+        FailureRecord {
+            condition: Some(condition),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_expression(self, expression: &'a str) -> Self {
+        FailureRecord {
+            expression: Some(expression),
+            ..self
+        }
+    }
+
+    pub(crate) fn with_stacktrace(self, stacktrace: &'a str) -> Self {
+        FailureRecord {
+            stacktrace: Some(stacktrace),
+            ..self
+        }
+    }
+
+    pub(crate) fn stacktrace(&self) -> Option<&'a str> {
+        self.stacktrace
+    }
+
+    /// The details that only some failures have, if any, recorded together
+    /// as the `unwrap.details` field.
+    pub(crate) fn details(&self) -> Option<Details<'_, 'a>> {
+        let any = self.pattern.is_some()
+            || self.condition.is_some()
+            || self.expression.is_some()
+            || self.attempt.is_some()
+            || self.retry_delay.is_some()
+            || self.address.is_some()
+            || self.suppressed.is_some();
+        any.then_some(Details(self))
+    }
+
+    /// The method or macro that failed, e.g. `"Result::unwrap_or_log"` or
+    /// `"guard_or_log!"`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// A stable identifier of the callsite, hashed from the file, line and
+    /// [`method`](Self::method), and recorded as the `unwrap.callsite_id`
+    /// field.
+    ///
+    /// Unlike the message, which may embed values, the identifier only
+    /// changes when the call moves or is replaced, and it uses a fixed hash
+    /// function, so it can be used to group failures across builds, releases
+    /// and toolchains.
+    pub fn callsite_id(&self) -> u64 {
+        callsite::id(self.location, self.method)
+    }
+
+    /// The error that caused the failure, when the value is known to be one,
+    /// whose source chain is logged.
+    pub fn error(&self) -> Option<&'a (dyn Error + 'static)> {
+        self.error
+    }
+
+    /// The type name of the error that caused the failure, for the methods of
+    /// [`ResultExt`](crate::ResultExt), recorded as the `exception.type` field
+    /// with the `otel-fields` feature.
+    pub fn error_type(&self) -> Option<&'static str> {
+        self.error_type
+    }
+
+    /// The number of the failed attempt, counting from 1, for failures of
+    /// [`retry_or_log`](crate::retry_or_log).
+    pub fn attempt(&self) -> Option<u32> {
+        self.attempt
+    }
+
+    /// The delay before the next attempt, for failures of
+    /// [`retry_or_log`](crate::retry_or_log) that are retried.
+    pub fn retry_delay(&self) -> Option<Duration> {
+        self.retry_delay
+    }
+
+    /// The kind of the I/O error that caused the failure, if it was one.
+    #[cfg(feature = "std")]
+    pub fn error_kind(&self) -> Option<io::ErrorKind> {
+        self.error_kind
+    }
+
+    /// The network address involved, for failures of the networking
+    /// extensions such as [`TcpStreamExt`](crate::TcpStreamExt).
+    pub fn address(&self) -> Option<&'a str> {
+        self.address
+    }
+
+    /// How many times the callsite has failed so far, including this time,
+    /// when failures are tracked per callsite: with the `callsite-inventory`
+    /// feature, or while an [`Escalation`](crate::Escalation) policy is
+    /// installed.
+    pub fn occurrence(&self) -> Option<u64> {
+        self.occurrence
+    }
+
+    /// How many failures at the callsite were suppressed by the
+    /// [`RateLimit`](crate::RateLimit) within a window, for the summary event
+    /// reported once the window closes.
+    pub fn suppressed(&self) -> Option<u64> {
+        self.suppressed
+    }
+
+    /// The failure message, either the one passed to an `expect_*` method or
+    /// a description of the failed call.
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+
+    /// The value that caused the failure, such as the content of an [`Err`],
+    /// if there is one.
+    pub fn value(&self) -> Option<&'a dyn fmt::Debug> {
+        self.value
+    }
+
+    /// The level the failure is logged at.
+    pub fn level(&self) -> tracing::Level {
+        self.level
+    }
+
+    /// The location of the failed call.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Whether the failure is followed by a panic, rather than the error
+    /// being discarded.
+    pub fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+
+    /// Whether the failure happened while the thread was already unwinding
+    /// from a panic, e.g. in a `Drop` implementation.
+    ///
+    /// Such a fatal failure aborts the process rather than panicking again.
+    pub fn is_during_unwind(&self) -> bool {
+        self.during_unwind
+    }
+
+    /// The pattern the value was expected to match, for failures of
+    /// [`matches_or_log!`](crate::matches_or_log).
+    pub fn pattern(&self) -> Option<&'a str> {
+        self.pattern
+    }
+
+    /// The source text of the condition that failed, for failures of
+    /// [`guard_or_log!`](crate::guard_or_log).
+    pub fn condition(&self) -> Option<&'a str> {
+        self.condition
+    }
+
+    /// The source text of the expression that failed to unwrap, for failures
+    /// of [`unwrap_or_log!`](crate::unwrap_or_log) and
+    /// [`expect_or_log!`](crate::expect_or_log).
+    pub fn expression(&self) -> Option<&'a str> {
+        self.expression
+    }
+}
+
+/// The details that only some failures have, such as the pattern of a
+/// [`matches_or_log!`](crate::matches_or_log), rendered together so that
+/// they take a single field of the event.
+pub(crate) struct Details<'r, 'a>(&'r FailureRecord<'a>);
+
+impl fmt::Debug for Details<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let record = self.0;
+        let mut map = f.debug_map();
+        let mut entry = |name: &str, value: Option<&dyn fmt::Debug>| {
+            if let Some(value) = value {
+                map.entry(&format_args!("{}", name), value);
+            }
+        };
+        entry("pattern", record.pattern.as_ref().map(|v| v as _));
+        entry("condition", record.condition.as_ref().map(|v| v as _));
+        entry("expression", record.expression.as_ref().map(|v| v as _));
+        entry("attempt", record.attempt.as_ref().map(|v| v as _));
+        entry("retry_delay", record.retry_delay.as_ref().map(|v| v as _));
+        entry("address", record.address.as_ref().map(|v| v as _));
+        entry("suppressed", record.suppressed.as_ref().map(|v| v as _));
+        map.finish()
+    }
+}
+
+impl fmt::Display for FailureRecord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{}: {:?}", self.message, value),
+            None => f.write_str(self.message),
+        }
+    }
+}
+
+impl fmt::Debug for FailureRecord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("FailureRecord");
+        debug
+            .field("method", &self.method)
+            .field("message", &self.message)
+            .field("value", &self.value)
+            .field("level", &self.level)
+            .field("location", &self.location)
+            .field("fatal", &self.fatal)
+            .field("during_unwind", &self.during_unwind)
+            .field("pattern", &self.pattern)
+            .field("condition", &self.condition)
+            .field("expression", &self.expression)
+            .field("error", &self.error)
+            .field("error_type", &self.error_type)
+            .field("attempt", &self.attempt)
+            .field("retry_delay", &self.retry_delay)
+            .field("address", &self.address)
+            .field("occurrence", &self.occurrence)
+            .field("suppressed", &self.suppressed);
+        #[cfg(feature = "std")]
+        debug.field("error_kind", &self.error_kind);
+        debug.finish()
+    }
+}
@@ -0,0 +1,111 @@
+//! Reporting when a callsite that kept discarding errors starts succeeding
+//! again.
+
+use core::panic::Location;
+#[cfg(feature = "std")]
+use {
+    crate::facade::Level,
+    crate::FailureRecord,
+    crate::{callsite, clock, dispatch, filter},
+    core::sync::atomic::{AtomicBool, Ordering},
+    core::time::Duration,
+    std::collections::HashMap,
+    std::sync::{Mutex, PoisonError},
+};
+
+#[cfg(feature = "std")]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The callsites whose last outcome was a logged failure.
+#[cfg(feature = "std")]
+static OUTAGES: Mutex<Option<HashMap<&'static Location<'static>, Outage>>> = Mutex::new(None);
+
+#[cfg(feature = "std")]
+struct Outage {
+    failures: u64,
+    since: Duration,
+}
+
+/// Enables or disables recovery events, which are disabled by default.
+///
+/// When enabled, the first success of a non-panicking method such as
+/// [`Result::ok_or_log`](crate::ResultExt::ok_or_log), at a callsite that
+/// logged one or more failures since its last success, emits an [`INFO`]
+/// event reading "recovered after N failures over D", so that dashboards
+/// show both an incident and its resolution.
+///
+/// The time is measured with the configured [`Clock`](crate::Clock).
+///
+/// [`INFO`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.INFO
+#[cfg(feature = "std")]
+pub fn set_recovery_events(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        *OUTAGES.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// Notes a logged, non-fatal failure, while recovery events are enabled.
+#[cfg(feature = "std")]
+pub(crate) fn failed(record: &FailureRecord<'_>) {
+    if record.is_fatal() || !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let mut outages = OUTAGES.lock().unwrap_or_else(PoisonError::into_inner);
+    let outage = outages
+        .get_or_insert_with(HashMap::new)
+        .entry(record.location())
+        .or_insert_with(|| Outage {
+            failures: 0,
+            since: clock::now(),
+        });
+    outage.failures += 1;
+}
+
+/// Notes a success at `location`, emitting a recovery event if it ends an
+/// outage.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn succeeded(method: &'static str, location: &'static Location<'static>) {
+    if ENABLED.load(Ordering::Relaxed) {
+        recovered(method, location);
+    }
+}
+
+/// Does nothing, as recovery events need the standard library.
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn succeeded(_method: &'static str, _location: &'static Location<'static>) {}
+
+#[cfg(feature = "std")]
+#[inline(never)]
+fn recovered(method: &'static str, location: &'static Location<'static>) {
+    let outage = match OUTAGES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+        .and_then(|outages| outages.remove(location))
+    {
+        Some(outage) => outage,
+        None => return,
+    };
+
+    if !filter::enabled(Level::INFO, location) {
+        return;
+    }
+
+    let duration = clock::now().saturating_sub(outage.since);
+    dispatch::with_dispatch(|| {
+        crate::facade::event!(
+            target: crate::TARGET,
+            Level::INFO,
+            unwrap.callsite_id = %format_args!("{:016x}", callsite::id(location, method)),
+            unwrap.method = method,
+            unwrap.failures = outage.failures,
+            "recovered after {} failures over {:?}",
+            outage.failures,
+            duration,
+        )
+    });
+}
@@ -0,0 +1,74 @@
+//! The process-wide funnel that every failure is reported through.
+
+use crate::FailureRecord;
+use std::sync::{PoisonError, RwLock};
+
+/// Something that failures are reported to, once they pass the filter.
+///
+/// The default reporter, [`TracingReporter`], emits a tracing event. Replace
+/// it with [`set_reporter`] to forward failures elsewhere as well, e.g. to an
+/// error-reporting hook, a crash server or a queue. Reporters can be chained
+/// by pairing them:
+///
+/// ```
+/// use tracing_unwrap::{FailureRecord, FailureReporter, TracingReporter};
+///
+/// struct CrashServer;
+///
+/// impl FailureReporter for CrashServer {
+///     fn report(&self, record: &FailureRecord<'_>) {
+///         if record.is_fatal() {
+///             // Upload the failure...
+///         }
+///     }
+/// }
+///
+/// tracing_unwrap::set_reporter(Some(Box::new((TracingReporter, CrashServer))));
+/// ```
+pub trait FailureReporter: Send + Sync {
+    /// Reports a failure. For fatal failures, this runs before the panic.
+    fn report(&self, record: &FailureRecord<'_>);
+}
+
+/// The default reporter, which emits a tracing event for every failure.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingReporter;
+
+impl FailureReporter for TracingReporter {
+    fn report(&self, record: &FailureRecord<'_>) {
+        crate::log(record);
+    }
+}
+
+/// Reports to both reporters, in order.
+impl<A: FailureReporter, B: FailureReporter> FailureReporter for (A, B) {
+    fn report(&self, record: &FailureRecord<'_>) {
+        self.0.report(record);
+        self.1.report(record);
+    }
+}
+
+impl<R: FailureReporter + ?Sized> FailureReporter for Box<R> {
+    fn report(&self, record: &FailureRecord<'_>) {
+        (**self).report(record);
+    }
+}
+
+static REPORTER: RwLock<Option<Box<dyn FailureReporter>>> = RwLock::new(None);
+
+/// Sets the reporter that every failure is reported to, replacing the
+/// [`TracingReporter`]. Pass `None` to restore it.
+///
+/// Keep [`TracingReporter`] in a chain to still have failures logged. A
+/// reporter must not call `set_reporter` itself.
+pub fn set_reporter(reporter: Option<Box<dyn FailureReporter>>) {
+    *REPORTER.write().unwrap_or_else(PoisonError::into_inner) = reporter;
+}
+
+pub(crate) fn report(record: &FailureRecord<'_>) {
+    let reporter = REPORTER.read().unwrap_or_else(PoisonError::into_inner);
+    match &*reporter {
+        Some(reporter) => reporter.report(record),
+        None => TracingReporter.report(record),
+    }
+}
@@ -0,0 +1,116 @@
+//! Extensions for fallible allocation in standard library collections.
+
+use crate::facade::Level;
+use crate::{callsite, emit, fail, recovery, FailureRecord};
+use std::collections::{HashMap, TryReserveError};
+use std::hash::{BuildHasher, Hash};
+use std::panic::Location;
+
+/// Extension trait for the `try_reserve` methods of [`Vec`], [`String`] and
+/// [`HashMap`], for services that handle allocation failures rather than
+/// aborting on them.
+pub trait TryReserveExt {
+    /// Reserves capacity for at least `additional` more elements, like
+    /// `try_reserve`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the allocation fails, logging the requested additional
+    /// capacity, the current capacity and the kind of the
+    /// [`TryReserveError`] to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn reserve_or_log(&mut self, additional: usize);
+
+    /// Reserves capacity for at least `additional` more elements, like
+    /// `try_reserve`, yielding whether it succeeded.
+    ///
+    /// A failed allocation is logged, with the requested additional capacity,
+    /// the current capacity and the kind of the [`TryReserveError`], to a
+    /// [`tracing::Subscriber`] at a [`WARN`] level.
+    ///
+    /// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+    fn try_reserve_or_log(&mut self, additional: usize) -> bool;
+}
+
+macro_rules! impl_try_reserve_ext {
+    ($ty:ty, $name:literal, [$($generics:tt)*] $(where $($bounds:tt)+)?) => {
+        impl<$($generics)*> TryReserveExt for $ty $(where $($bounds)+)? {
+            #[inline]
+            #[track_caller]
+            fn reserve_or_log(&mut self, additional: usize) {
+                callsite::register(concat!($name, "::reserve_or_log"));
+                if let Err(e) = self.try_reserve(additional) {
+                    reserve_failed(
+                        concat!($name, "::reserve_or_log"),
+                        concat!("called `", $name, "::reserve_or_log()` and the allocation failed"),
+                        additional,
+                        self.capacity(),
+                        &e,
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn try_reserve_or_log(&mut self, additional: usize) -> bool {
+                callsite::register(concat!($name, "::try_reserve_or_log"));
+                match self.try_reserve(additional) {
+                    Ok(()) => {
+                        recovery::succeeded(concat!($name, "::try_reserve_or_log"), Location::caller());
+                        true
+                    }
+                    Err(e) => {
+                        reserve_discarded(
+                            concat!($name, "::try_reserve_or_log"),
+                            concat!("called `", $name, "::try_reserve_or_log()` and the allocation failed"),
+                            additional,
+                            self.capacity(),
+                            &e,
+                        );
+                        false
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_try_reserve_ext!(Vec<T>, "Vec", [T]);
+impl_try_reserve_ext!(String, "String", []);
+impl_try_reserve_ext!(HashMap<K, V, S>, "HashMap", [K, V, S] where K: Eq + Hash, S: BuildHasher);
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn reserve_failed(
+    method: &'static str,
+    msg: &str,
+    additional: usize,
+    capacity: usize,
+    error: &TryReserveError,
+) -> ! {
+    let value = format_args!(
+        "additional {}, capacity {}, {:?}",
+        additional, capacity, error
+    );
+    fail(&FailureRecord::new(method, msg, Some(&value), Level::ERROR, true).with_error(error))
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn reserve_discarded(
+    method: &'static str,
+    msg: &str,
+    additional: usize,
+    capacity: usize,
+    error: &TryReserveError,
+) {
+    let value = format_args!(
+        "additional {}, capacity {}, {:?}",
+        additional, capacity, error
+    );
+    emit(&FailureRecord::new(method, msg, Some(&value), Level::WARN, false).with_error(error));
+}
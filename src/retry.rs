@@ -0,0 +1,140 @@
+//! Retries of fallible async operations, on the tokio runtime.
+
+use crate::{callsite, emit, span_capture, FailureRecord};
+use std::fmt;
+use std::future::Future;
+use std::panic::Location;
+use std::time::Duration;
+
+/// How often, and how far apart, [`retry_or_log`] attempts an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    factor: u32,
+    max_delay: Duration,
+    max_attempts: u32,
+}
+
+impl Backoff {
+    /// Waits `delay` between attempts, for 3 attempts unless
+    /// [`with_max_attempts`](Self::with_max_attempts) says otherwise.
+    pub fn fixed(delay: Duration) -> Self {
+        Backoff {
+            initial: delay,
+            factor: 1,
+            max_delay: delay,
+            max_attempts: 3,
+        }
+    }
+
+    /// Waits `initial` before the first retry and twice as long before each
+    /// further one, for 3 attempts unless
+    /// [`with_max_attempts`](Self::with_max_attempts) says otherwise.
+    pub fn exponential(initial: Duration) -> Self {
+        Backoff {
+            initial,
+            factor: 2,
+            max_delay: Duration::MAX,
+            max_attempts: 3,
+        }
+    }
+
+    /// Sets how many attempts are made in total, at least one.
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        Backoff {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    /// Caps the delay between attempts.
+    pub fn with_max_delay(self, max_delay: Duration) -> Self {
+        Backoff { max_delay, ..self }
+    }
+
+    /// The delay after the failed `attempt`, counting from 1.
+    fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = self.factor.saturating_pow(attempt - 1);
+        self.initial
+            .checked_mul(factor)
+            .unwrap_or(Duration::MAX)
+            .min(self.max_delay)
+    }
+}
+
+/// Runs an async `operation` until it succeeds or `backoff` runs out of
+/// attempts, yielding its output or its last error.
+///
+/// Each failed attempt that is retried is logged with the operation `label`,
+/// its error, the attempt number and the delay before the next one to a
+/// [`tracing::Subscriber`] at a [`WARN`] level. Only once the attempts are
+/// exhausted is the last error logged at an [`ERROR`] level.
+///
+/// The location of this call is captured when the future is created, and
+/// reported for failures even though they happen when the future is polled.
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[track_caller]
+pub fn retry_or_log<'a, T, E, F, Fut>(
+    label: &'a str,
+    backoff: Backoff,
+    mut operation: F,
+) -> impl Future<Output = Result<T, E>> + 'a
+where
+    E: fmt::Debug,
+    F: FnMut() -> Fut + 'a,
+    Fut: Future<Output = Result<T, E>> + 'a,
+{
+    callsite::register("retry_or_log");
+    let location = Location::caller();
+    let span = span_capture::capture();
+    async move {
+        let mut attempt = 1;
+        loop {
+            let error = match operation().await {
+                Ok(t) => return Ok(t),
+                Err(e) => e,
+            };
+
+            if attempt == backoff.max_attempts {
+                let msg = format!("operation `{}` failed after {} attempts", label, attempt);
+                let record = FailureRecord::new(
+                    "retry_or_log",
+                    &msg,
+                    Some(&error),
+                    crate::facade::Level::ERROR,
+                    false,
+                );
+                let _span = span_capture::enter(&span);
+                emit(&record.with_location(location).with_attempt(attempt, None));
+                return Err(error);
+            }
+
+            let delay = backoff.delay_after(attempt);
+            let msg = format!(
+                "attempt {} of operation `{}` failed, retrying in {:?}",
+                attempt, label, delay
+            );
+            let record = FailureRecord::new(
+                "retry_or_log",
+                &msg,
+                Some(&error),
+                crate::facade::Level::WARN,
+                false,
+            );
+            {
+                let _span = span_capture::enter(&span);
+                emit(
+                    &record
+                        .with_location(location)
+                        .with_attempt(attempt, Some(delay)),
+                );
+            }
+            drop(error);
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
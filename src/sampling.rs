@@ -0,0 +1,32 @@
+//! Hinting tail-sampling collectors to retain the traces of fatal failures.
+
+use crate::FailureRecord;
+use std::sync::{PoisonError, RwLock};
+
+static PRIORITY: RwLock<Option<i64>> = RwLock::new(None);
+
+/// Sets a sampling priority, e.g. `Some(1)` or Datadog's `Some(2)` for
+/// "user keep", that fatal failures record as the `sampling.priority` field,
+/// both on their event and on the current span if it declares that field.
+/// Pass `None` to stop recording it.
+///
+/// Tail-sampling collectors can then be configured to always retain traces
+/// that contain a fatal unwrap. For anything more involved, such as calling
+/// a tracer's API, use a [`FailureReporter`](crate::FailureReporter).
+pub fn set_sampling_priority(priority: Option<i64>) {
+    *PRIORITY.write().unwrap_or_else(PoisonError::into_inner) = priority;
+}
+
+/// Returns the sampling priority for a failure, recording it on the current
+/// span as well.
+pub(crate) fn priority(record: &FailureRecord<'_>) -> Option<i64> {
+    if !record.is_fatal() {
+        return None;
+    }
+
+    let priority = *PRIORITY.read().unwrap_or_else(PoisonError::into_inner);
+    if let Some(priority) = priority {
+        crate::facade::Span::current().record("sampling.priority", priority);
+    }
+    priority
+}
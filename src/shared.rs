@@ -0,0 +1,195 @@
+//! Extensions for the reference-counted pointers [`Arc`] and [`Rc`].
+
+use crate::{callsite, failed_with};
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+/// Extension trait for [`Arc`] pointers.
+pub trait ArcExt<T: ?Sized> {
+    /// Returns the inner value, if the [`Arc`] has exactly one strong reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Arc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn try_unwrap_or_log(self) -> T
+    where
+        T: Sized;
+
+    /// Returns the inner value, if the [`Arc`] has exactly one strong reference.
+    /// Unlike [`try_unwrap_or_log`](ArcExt::try_unwrap_or_log), the [`Arc`] is
+    /// always consumed, so concurrent callers can't both fail to unwrap it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Arc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn into_inner_or_log(self) -> T
+    where
+        T: Sized;
+
+    /// Returns a mutable reference into the given [`Arc`], if there are no
+    /// other [`Arc`] or [`Weak`](alloc::sync::Weak) pointers to the same allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Arc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn get_mut_or_log(&mut self) -> &mut T;
+}
+
+impl<T: ?Sized> ArcExt<T> for Arc<T> {
+    #[inline]
+    #[track_caller]
+    fn try_unwrap_or_log(self) -> T
+    where
+        T: Sized,
+    {
+        callsite::register("Arc::try_unwrap_or_log");
+        match Arc::try_unwrap(self) {
+            Ok(t) => t,
+            Err(this) => failed_with(
+                "Arc::try_unwrap_or_log",
+                "called `Arc::try_unwrap_or_log()` on a shared `Arc`",
+                &format_args!(
+                    "strong count {}, weak count {}",
+                    Arc::strong_count(&this),
+                    Arc::weak_count(&this)
+                ),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_inner_or_log(self) -> T
+    where
+        T: Sized,
+    {
+        callsite::register("Arc::into_inner_or_log");
+        let (strong, weak) = (Arc::strong_count(&self), Arc::weak_count(&self));
+        match Arc::into_inner(self) {
+            Some(t) => t,
+            None => failed_with(
+                "Arc::into_inner_or_log",
+                "called `Arc::into_inner_or_log()` on a shared `Arc`",
+                &format_args!("strong count {}, weak count {}", strong, weak),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn get_mut_or_log(&mut self) -> &mut T {
+        callsite::register("Arc::get_mut_or_log");
+        let (strong, weak) = (Arc::strong_count(self), Arc::weak_count(self));
+        match Arc::get_mut(self) {
+            Some(t) => t,
+            None => failed_with(
+                "Arc::get_mut_or_log",
+                "called `Arc::get_mut_or_log()` on a shared `Arc`",
+                &format_args!("strong count {}, weak count {}", strong, weak),
+            ),
+        }
+    }
+}
+
+/// Extension trait for [`Rc`] pointers.
+pub trait RcExt<T: ?Sized> {
+    /// Returns the inner value, if the [`Rc`] has exactly one strong reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Rc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn try_unwrap_or_log(self) -> T
+    where
+        T: Sized;
+
+    /// Returns the inner value, if the [`Rc`] has exactly one strong reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Rc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn into_inner_or_log(self) -> T
+    where
+        T: Sized;
+
+    /// Returns a mutable reference into the given [`Rc`], if there are no
+    /// other [`Rc`] or [`Weak`](alloc::rc::Weak) pointers to the same allocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Rc`] is still shared, logging its strong and weak
+    /// counts to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn get_mut_or_log(&mut self) -> &mut T;
+}
+
+impl<T: ?Sized> RcExt<T> for Rc<T> {
+    #[inline]
+    #[track_caller]
+    fn try_unwrap_or_log(self) -> T
+    where
+        T: Sized,
+    {
+        callsite::register("Rc::try_unwrap_or_log");
+        match Rc::try_unwrap(self) {
+            Ok(t) => t,
+            Err(this) => failed_with(
+                "Rc::try_unwrap_or_log",
+                "called `Rc::try_unwrap_or_log()` on a shared `Rc`",
+                &format_args!(
+                    "strong count {}, weak count {}",
+                    Rc::strong_count(&this),
+                    Rc::weak_count(&this)
+                ),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn into_inner_or_log(self) -> T
+    where
+        T: Sized,
+    {
+        callsite::register("Rc::into_inner_or_log");
+        let (strong, weak) = (Rc::strong_count(&self), Rc::weak_count(&self));
+        match Rc::into_inner(self) {
+            Some(t) => t,
+            None => failed_with(
+                "Rc::into_inner_or_log",
+                "called `Rc::into_inner_or_log()` on a shared `Rc`",
+                &format_args!("strong count {}, weak count {}", strong, weak),
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn get_mut_or_log(&mut self) -> &mut T {
+        callsite::register("Rc::get_mut_or_log");
+        let (strong, weak) = (Rc::strong_count(self), Rc::weak_count(self));
+        match Rc::get_mut(self) {
+            Some(t) => t,
+            None => failed_with(
+                "Rc::get_mut_or_log",
+                "called `Rc::get_mut_or_log()` on a shared `Rc`",
+                &format_args!("strong count {}, weak count {}", strong, weak),
+            ),
+        }
+    }
+}
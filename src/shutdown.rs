@@ -0,0 +1,91 @@
+//! A hook that gets to shut a process down gracefully before a fatal failure
+//! panics.
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+type Hook = Box<dyn FnOnce() + Send>;
+
+static SHUTDOWN: Mutex<Option<(Hook, Duration)>> = Mutex::new(None);
+
+/// Registers a hook that the first fatal failure runs before panicking, e.g.
+/// to drain in-flight requests or flush exporters. The failure waits for the
+/// hook for at most `timeout`, then panics regardless.
+///
+/// The hook runs on a thread of its own, and at most once: later failures
+/// panic straight away, unless another hook is registered.
+pub fn set_shutdown_hook<F>(hook: F, timeout: Duration)
+where
+    F: FnOnce() + Send + 'static,
+{
+    *SHUTDOWN.lock().unwrap_or_else(PoisonError::into_inner) = Some((Box::new(hook), timeout));
+}
+
+/// Registers an asynchronous hook that the first fatal failure runs before
+/// panicking, as with [`set_shutdown_hook`].
+///
+/// The future is driven by a minimal executor on a thread of its own. If it
+/// depends on a runtime such as tokio, register a blocking hook that enters
+/// the runtime instead, e.g. with `Handle::block_on`.
+pub fn set_async_shutdown_hook<F, Fut>(hook: F, timeout: Duration)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()>,
+{
+    set_shutdown_hook(move || block_on(hook()), timeout);
+}
+
+/// Runs the registered shutdown hook, if any, waiting for it up to its timeout.
+pub(crate) fn run() {
+    let Some((hook, timeout)) = SHUTDOWN
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+    else {
+        return;
+    };
+
+    let (done, finished) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name("tracing-unwrap-shutdown".into())
+        .spawn(move || {
+            hook();
+            let _ = done.send(());
+        });
+
+    match spawned {
+        Ok(_) => {
+            if let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(timeout) {
+                crate::facade::warn!(target: crate::TARGET, "shutdown hook did not finish within {:?}", timeout);
+            }
+        }
+        Err(e) => {
+            crate::facade::warn!(target: crate::TARGET, "failed to spawn the shutdown hook thread: {}", e)
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Rendering of error source chains, up to a configurable depth.
+
+use alloc::string::{String, ToString};
+use core::error::Error;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static MAX_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DEPTH);
+
+/// The number of sources logged unless [`set_max_source_depth`] says
+/// otherwise.
+const DEFAULT_MAX_DEPTH: usize = 16;
+
+/// Sets how many sources of an error are logged, 16 by default.
+///
+/// A longer source chain is cut at that depth and marked by the
+/// `unwrap.sources_truncated` field, so that a pathological or cyclic chain
+/// can't produce unbounded output.
+pub fn set_max_source_depth(depth: usize) {
+    MAX_DEPTH.store(depth, Ordering::Relaxed);
+}
+
+/// The configured maximum depth of a source chain.
+pub(crate) fn max_depth() -> usize {
+    MAX_DEPTH.load(Ordering::Relaxed)
+}
+
+/// The rendered sources of an error.
+pub(crate) struct Sources {
+    /// The `Display` renderings of the sources, separated by `": "`.
+    pub(crate) chain: String,
+    /// The depth the chain was cut at, if it was longer.
+    pub(crate) truncated_at: Option<usize>,
+}
+
+/// Renders the source chain of `error`, not including `error` itself. Returns
+/// `None` if the error has no source.
+pub(crate) fn render(error: &dyn Error) -> Option<Sources> {
+    let max_depth = max_depth();
+    let mut chain = String::new();
+    let mut source = error.source();
+    let mut depth = 0;
+    while let Some(error) = source {
+        if depth == max_depth {
+            return Some(Sources {
+                chain,
+                truncated_at: Some(depth),
+            });
+        }
+        if depth > 0 {
+            chain.push_str(": ");
+        }
+        chain.push_str(&error.to_string());
+        source = error.source();
+        depth += 1;
+    }
+
+    (depth > 0).then_some(Sources {
+        chain,
+        truncated_at: None,
+    })
+}
+
+/// Whether the source chain of `error` is no deeper than the configured
+/// maximum depth, so that it can be handed to a subscriber that walks it in
+/// full.
+#[cfg(all(feature = "structured-errors", feature = "std"))]
+pub(crate) fn within_max_depth(error: &dyn Error) -> bool {
+    let max_depth = max_depth();
+    let mut source = error.source();
+    let mut depth = 0;
+    while let Some(error) = source {
+        if depth == max_depth {
+            return false;
+        }
+        source = error.source();
+        depth += 1;
+    }
+    true
+}
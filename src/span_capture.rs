@@ -0,0 +1,67 @@
+//! The span that the failure events of async adapters are emitted in.
+
+#[cfg(feature = "std")]
+use crate::facade::{Entered, Span};
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "std")]
+static AT_CONSTRUCTION: AtomicBool = AtomicBool::new(false);
+
+/// Which span the failure events of async adapters, such as
+/// [`ResultFutureExt::unwrap_or_log`](crate::ResultFutureExt::unwrap_or_log),
+/// are emitted in.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpanCapture {
+    /// The span that is current when the future is polled, as for any other
+    /// event. This is the default.
+    #[default]
+    Poll,
+    /// The span that was current when the future was created.
+    ///
+    /// Executors often poll futures in an unrelated span, or in none at all,
+    /// unless they are instrumented; this keeps the request context of the
+    /// code that set the future up.
+    Construction,
+}
+
+/// Sets which span the failure events of async adapters are emitted in, for
+/// adapters that don't choose for themselves, as the `_in_current_span`
+/// forms do. Futures that were already created keep the setting they were
+/// created with.
+#[cfg(feature = "std")]
+pub fn set_async_span_capture(capture: SpanCapture) {
+    AT_CONSTRUCTION.store(capture == SpanCapture::Construction, Ordering::Relaxed);
+}
+
+/// The span captured when an async adapter was created, if any. Without the
+/// standard library, there are no spans to capture.
+#[cfg(feature = "std")]
+pub(crate) type Captured = Option<Span>;
+#[cfg(not(feature = "std"))]
+pub(crate) struct Captured;
+
+/// Captures the current span, if failure events are to be emitted in it,
+/// when an async adapter is created.
+#[cfg(feature = "std")]
+pub(crate) fn capture() -> Captured {
+    AT_CONSTRUCTION.load(Ordering::Relaxed).then(Span::current)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn capture() -> Captured {
+    Captured
+}
+
+/// Enters a captured span, if any, for emitting a failure event.
+#[cfg(feature = "std")]
+pub(crate) fn enter(span: &Captured) -> Option<Entered<'_>> {
+    span.as_ref().map(Span::enter)
+}
+
+/// There is no span to enter without the standard library.
+#[cfg(not(feature = "std"))]
+pub(crate) fn enter(span: &Captured) -> &Captured {
+    span
+}
@@ -0,0 +1,187 @@
+//! Extensions for splitting and stripping string slices.
+
+use crate::{callsite, failed_with};
+use core::fmt;
+
+/// Input longer than this many characters is truncated in the logged preview.
+const PREVIEW_CHARS: usize = 64;
+
+/// A delimiter accepted by the [`StrExt`] methods: a `char` or a `&str`.
+///
+/// This stands in for `std`'s unstable `Pattern` trait and cannot be
+/// implemented outside this crate.
+pub trait Delimiter: fmt::Debug + Copy + sealed::Sealed {
+    #[doc(hidden)]
+    fn split_once(self, s: &str) -> Option<(&str, &str)>;
+    #[doc(hidden)]
+    fn rsplit_once(self, s: &str) -> Option<(&str, &str)>;
+    #[doc(hidden)]
+    fn strip_prefix(self, s: &str) -> Option<&str>;
+    #[doc(hidden)]
+    fn strip_suffix(self, s: &str) -> Option<&str>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for char {}
+    impl Sealed for &str {}
+}
+
+macro_rules! impl_delimiter {
+    ($($ty:ty),+) => {$(
+        impl Delimiter for $ty {
+            #[inline]
+            fn split_once(self, s: &str) -> Option<(&str, &str)> {
+                s.split_once(self)
+            }
+            #[inline]
+            fn rsplit_once(self, s: &str) -> Option<(&str, &str)> {
+                s.rsplit_once(self)
+            }
+            #[inline]
+            fn strip_prefix(self, s: &str) -> Option<&str> {
+                s.strip_prefix(self)
+            }
+            #[inline]
+            fn strip_suffix(self, s: &str) -> Option<&str> {
+                s.strip_suffix(self)
+            }
+        }
+    )+};
+}
+
+impl_delimiter!(char, &str);
+
+/// Extension trait for string slices.
+pub trait StrExt {
+    /// Splits the string on the first occurrence of `delim`, returning the
+    /// parts before and after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delim` does not occur in the string, logging the delimiter
+    /// and a preview of the string to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn split_once_or_log<D: Delimiter>(&self, delim: D) -> (&str, &str);
+
+    /// Splits the string on the last occurrence of `delim`, returning the
+    /// parts before and after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delim` does not occur in the string, logging the delimiter
+    /// and a preview of the string to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn rsplit_once_or_log<D: Delimiter>(&self, delim: D) -> (&str, &str);
+
+    /// Returns the string with `prefix` removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string does not start with `prefix`, logging the prefix
+    /// and a preview of the string to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn strip_prefix_or_log<D: Delimiter>(&self, prefix: D) -> &str;
+
+    /// Returns the string with `suffix` removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string does not end with `suffix`, logging the suffix
+    /// and a preview of the string to a [`tracing::Subscriber`] at an
+    /// [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn strip_suffix_or_log<D: Delimiter>(&self, suffix: D) -> &str;
+}
+
+impl StrExt for str {
+    #[inline]
+    #[track_caller]
+    fn split_once_or_log<D: Delimiter>(&self, delim: D) -> (&str, &str) {
+        callsite::register("str::split_once_or_log");
+        match delim.split_once(self) {
+            Some(parts) => parts,
+            None => not_found(
+                "str::split_once_or_log",
+                "called `str::split_once_or_log()` without a match",
+                delim,
+                self,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn rsplit_once_or_log<D: Delimiter>(&self, delim: D) -> (&str, &str) {
+        callsite::register("str::rsplit_once_or_log");
+        match delim.rsplit_once(self) {
+            Some(parts) => parts,
+            None => not_found(
+                "str::rsplit_once_or_log",
+                "called `str::rsplit_once_or_log()` without a match",
+                delim,
+                self,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn strip_prefix_or_log<D: Delimiter>(&self, prefix: D) -> &str {
+        callsite::register("str::strip_prefix_or_log");
+        match prefix.strip_prefix(self) {
+            Some(rest) => rest,
+            None => not_found(
+                "str::strip_prefix_or_log",
+                "called `str::strip_prefix_or_log()` without a match",
+                prefix,
+                self,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn strip_suffix_or_log<D: Delimiter>(&self, suffix: D) -> &str {
+        callsite::register("str::strip_suffix_or_log");
+        match suffix.strip_suffix(self) {
+            Some(rest) => rest,
+            None => not_found(
+                "str::strip_suffix_or_log",
+                "called `str::strip_suffix_or_log()` without a match",
+                suffix,
+                self,
+            ),
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn not_found(method: &'static str, msg: &str, delim: impl Delimiter, input: &str) -> ! {
+    failed_with(
+        method,
+        msg,
+        &format_args!("delimiter {:?}, input {:?}", delim, Preview(input)),
+    )
+}
+
+/// Renders a string like its `Debug` form, truncated to [`PREVIEW_CHARS`].
+struct Preview<'a>(&'a str);
+
+impl fmt::Debug for Preview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.char_indices().nth(PREVIEW_CHARS) {
+            Some((end, _)) => write!(f, "{:?}...", &self.0[..end]),
+            None => write!(f, "{:?}", self.0),
+        }
+    }
+}
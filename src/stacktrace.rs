@@ -0,0 +1,63 @@
+//! Backtraces of failures, trimmed to start at the failed call.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::fmt::Write;
+
+/// Captures a backtrace of the current failure, rendered without the frames
+/// of the backtrace machinery and of this crate, so that it starts at the
+/// caller of the failed method. Returns `None` if backtraces aren't supported
+/// on the platform.
+pub(crate) fn capture() -> Option<String> {
+    let backtrace = Backtrace::force_capture();
+    if backtrace.status() != BacktraceStatus::Captured {
+        return None;
+    }
+    Some(trim(&backtrace.to_string()))
+}
+
+/// Drops the leading frames that belong to the backtrace or panic machinery,
+/// to `tracing` or to this crate, from a rendered backtrace, then renumbers
+/// the remaining frames.
+fn trim(rendered: &str) -> String {
+    // Each frame is a numbered line with its symbol, followed by indented
+    // `at file:line` lines.
+    let mut frames: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in rendered.lines() {
+        match line.trim_start().split_once(": ") {
+            Some((number, symbol)) if number.bytes().all(|b| b.is_ascii_digit()) => {
+                frames.push((symbol, Vec::new()))
+            }
+            _ => {
+                if let Some((_, details)) = frames.last_mut() {
+                    details.push(line);
+                }
+            }
+        }
+    }
+
+    let internal = |symbol: &str| {
+        symbol.starts_with("std::backtrace")
+            || symbol.starts_with("std::panicking")
+            || symbol.starts_with("core::panicking")
+            || symbol.starts_with("__rustc")
+            || symbol.contains("rust_begin_unwind")
+            || symbol.contains("tracing_unwrap::")
+            // A dispatcher set with `set_dispatch` runs the event in
+            // `tracing`'s scoped dispatcher, between the crate's frames.
+            || symbol.starts_with("tracing_core::")
+            || symbol.starts_with("tracing::")
+    };
+    let skipped = frames
+        .iter()
+        .take_while(|(symbol, _)| internal(symbol))
+        .count();
+
+    let mut trimmed = String::new();
+    for (number, (symbol, details)) in frames[skipped..].iter().enumerate() {
+        let _ = writeln!(trimmed, "{:4}: {}", number, symbol);
+        for line in details {
+            let _ = writeln!(trimmed, "{}", line);
+        }
+    }
+    trimmed
+}
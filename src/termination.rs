@@ -0,0 +1,56 @@
+//! Writing a summary of the fatal failure to a container termination log.
+
+use crate::FailureRecord;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::{PoisonError, RwLock};
+
+/// The path Kubernetes reads a container's termination message from, unless
+/// the pod's `terminationMessagePath` says otherwise.
+const DEFAULT_PATH: &str = "/dev/termination-log";
+
+/// Kubernetes keeps at most this many bytes of a termination message.
+const MAX_LEN: usize = 4096;
+
+static PATH: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets the file that a fatal failure writes its summary to before
+/// panicking, by default `/dev/termination-log`.
+///
+/// This should match the `terminationMessagePath` of the container, so that
+/// `kubectl describe pod` shows why it died even if its logs were lost.
+pub fn set_termination_log_path(path: impl Into<PathBuf>) {
+    *PATH.write().unwrap_or_else(PoisonError::into_inner) = Some(path.into());
+}
+
+/// Overwrites the termination log with the message, location and callsite of
+/// a failure.
+pub(crate) fn write(record: &FailureRecord<'_>) {
+    let location = record.location();
+    let mut summary = format!(
+        "{}\nat {}:{}:{}\ncallsite {:016x} ({})\n",
+        record,
+        location.file(),
+        location.line(),
+        location.column(),
+        record.callsite_id(),
+        record.method(),
+    );
+    if summary.len() > MAX_LEN {
+        let mut end = MAX_LEN - "…\n".len();
+        while !summary.is_char_boundary(end) {
+            end -= 1;
+        }
+        summary.truncate(end);
+        let _ = summary.write_str("…\n");
+    }
+
+    let path = PATH
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_PATH));
+    if let Err(e) = std::fs::write(&path, summary) {
+        crate::facade::warn!(target: crate::TARGET, "failed to write the termination log {:?}: {}", path, e);
+    }
+}
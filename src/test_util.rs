@@ -0,0 +1,215 @@
+//! Helpers for tests that exercise failing unwraps.
+//!
+//! The test harness captures a test's output and shows it when the test
+//! fails, but what a failed unwrap logged only appears there if the test has
+//! set up a subscriber. A [`Replay`] guard buffers this crate's events on the
+//! current thread instead, and prints them after the panic message if the
+//! test panics.
+//!
+//! ```
+//! // At the start of a test:
+//! let _replay = tracing_unwrap::test_util::Replay::start();
+//! ```
+
+use crate::facade::Level;
+use crate::{context, instance, sources, FailureRecord};
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::Location;
+
+thread_local! {
+    static BUFFER: RefCell<Option<Vec<ReplayedEvent>>> = const { RefCell::new(None) };
+}
+
+/// A failure event buffered by a [`Replay`] guard, with the fields it was
+/// recorded with.
+///
+/// Its [`Display`](fmt::Display) implementation renders the event as it is
+/// replayed: its level, location and message, followed by its fields.
+#[derive(Clone, Debug)]
+pub struct ReplayedEvent {
+    level: Level,
+    location: &'static Location<'static>,
+    method: &'static str,
+    message: String,
+    /// The fields that were set, by name, rendered as a formatting
+    /// subscriber renders them.
+    fields: Vec<(&'static str, String)>,
+    during_unwind: bool,
+}
+
+impl ReplayedEvent {
+    /// The level the event was emitted at.
+    pub fn level(&self) -> tracing::Level {
+        self.level
+    }
+
+    /// The location of the failed call.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The method or macro that failed.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The message of the event, as formatted for the subscriber.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The `Debug` rendering of the value that caused the failure, if there
+    /// was one.
+    pub fn value(&self) -> Option<&str> {
+        self.field("unwrap.value")
+    }
+
+    /// The field called `name`, such as `"unwrap.callsite_id"` or
+    /// `"unwrap.sources"`, if the event set it, rendered as a formatting
+    /// subscriber renders it: strings are quoted, numbers are not.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The fields the event set, in the order they are replayed.
+    pub fn fields(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+    }
+
+    /// Whether the failure happened while the thread was already unwinding.
+    pub fn is_during_unwind(&self) -> bool {
+        self.during_unwind
+    }
+}
+
+impl fmt::Display for ReplayedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}:{}:{}: {}",
+            self.level,
+            self.location.file(),
+            self.location.line(),
+            self.location.column(),
+            self.message,
+        )?;
+        for (name, value) in &self.fields {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A guard that buffers the failure events emitted on the current thread, and
+/// prints them to `stderr` if it is dropped during a panic.
+#[must_use = "events are only buffered while the guard is alive"]
+pub struct Replay {
+    previous: Option<Vec<ReplayedEvent>>,
+}
+
+impl Replay {
+    /// Starts buffering failure events on the current thread, until the
+    /// returned guard is dropped.
+    pub fn start() -> Self {
+        let previous = BUFFER.with(|buffer| buffer.replace(Some(Vec::new())));
+        Replay { previous }
+    }
+
+    /// Returns the events buffered so far.
+    pub fn events(&self) -> Vec<ReplayedEvent> {
+        BUFFER.with(|buffer| buffer.borrow().clone().unwrap_or_default())
+    }
+}
+
+impl Drop for Replay {
+    fn drop(&mut self) {
+        let events = BUFFER.with(|buffer| buffer.replace(self.previous.take()));
+        if std::thread::panicking() {
+            let events = events.unwrap_or_default();
+            if !events.is_empty() {
+                eprintln!("tracing-unwrap events before the panic:");
+                for event in events {
+                    eprintln!("  {}", event);
+                }
+            }
+        }
+    }
+}
+
+/// Runs `f`, printing the failure events it emitted if it panics.
+pub fn replay_on_panic<R>(f: impl FnOnce() -> R) -> R {
+    let _replay = Replay::start();
+    f()
+}
+
+/// Buffers an emitted event, if a [`Replay`] is active on this thread.
+pub(crate) fn capture(record: &FailureRecord<'_>, msg: &str) {
+    BUFFER.with(|buffer| {
+        if let Some(events) = buffer.borrow_mut().as_mut() {
+            events.push(ReplayedEvent {
+                level: record.level(),
+                location: record.location(),
+                method: record.method(),
+                message: msg.to_string(),
+                fields: fields(record),
+                during_unwind: record.is_during_unwind(),
+            });
+        }
+    });
+}
+
+/// Renders the fields that the event for `record` sets, other than its
+/// location, backtrace and build metadata.
+fn fields(record: &FailureRecord<'_>) -> Vec<(&'static str, String)> {
+    let sources = record.error().and_then(sources::render);
+    let fields = [
+        (
+            "unwrap.callsite_id",
+            Some(format!("{:016x}", record.callsite_id())),
+        ),
+        ("unwrap.method", Some(format!("{:?}", record.method()))),
+        ("unwrap.value", record.value().map(|v| format!("{:?}", v))),
+        (
+            "unwrap.details",
+            record.details().map(|d| format!("{:?}", d)),
+        ),
+        (
+            "unwrap.sources",
+            sources.as_ref().map(|s| format!("{:?}", s.chain)),
+        ),
+        (
+            "unwrap.sources_truncated",
+            sources.and_then(|s| s.truncated_at).map(|d| d.to_string()),
+        ),
+        (
+            "unwrap.error_kind",
+            record.error_kind().map(|k| format!("{:?}", k)),
+        ),
+        (
+            "unwrap.instance_id",
+            instance::current().map(|id| format!("{:?}", id)),
+        ),
+        (
+            "unwrap.context",
+            context::current().map(|c| format!("{:?}", c)),
+        ),
+        (
+            "unwrap.occurrence",
+            record.occurrence().map(|o| o.to_string()),
+        ),
+        (
+            "unwrap.during_unwind",
+            record.is_during_unwind().then(|| "true".to_string()),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+}
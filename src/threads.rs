@@ -0,0 +1,294 @@
+//! Extensions for spawning and joining standard library threads.
+
+use crate::{callsite, fail, failed_with, FailureRecord, UnwrapFailure};
+use std::any::Any;
+use std::collections::HashMap;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, Builder, JoinHandle, Scope, ScopedJoinHandle};
+
+/// The panics of the threads spawned with
+/// [`spawn_scoped_or_log`](BuilderExt::spawn_scoped_or_log) into each scope
+/// that [`scope_or_log`] is running, by the address of the scope.
+static SCOPES: Mutex<Option<HashMap<usize, Vec<String>>>> = Mutex::new(None);
+
+/// Extension trait for [`JoinHandle`]s of standard library threads, and for
+/// the [`ScopedJoinHandle`]s of scoped threads.
+pub trait JoinHandleExt<T> {
+    /// Waits for the thread to finish, yielding its result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread panicked, logging the thread's name and its panic
+    /// message to a [`tracing::Subscriber`] at an [`ERROR`] level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn join_or_log(self) -> T;
+}
+
+impl<T> JoinHandleExt<T> for JoinHandle<T> {
+    #[inline]
+    #[track_caller]
+    fn join_or_log(self) -> T {
+        callsite::register("JoinHandle::join_or_log");
+        let name = self.thread().name().map(str::to_owned);
+        match self.join() {
+            Ok(t) => t,
+            Err(payload) => thread_panicked(
+                "JoinHandle::join_or_log",
+                "called `JoinHandle::join_or_log()` on a thread that panicked",
+                name.as_deref(),
+                &*payload,
+            ),
+        }
+    }
+}
+
+impl<T> JoinHandleExt<T> for ScopedJoinHandle<'_, T> {
+    #[inline]
+    #[track_caller]
+    fn join_or_log(self) -> T {
+        callsite::register("ScopedJoinHandle::join_or_log");
+        let name = self.thread().name().map(str::to_owned);
+        match self.join() {
+            Ok(t) => t,
+            Err(payload) => thread_panicked(
+                "ScopedJoinHandle::join_or_log",
+                "called `ScopedJoinHandle::join_or_log()` on a thread that panicked",
+                name.as_deref(),
+                &*payload,
+            ),
+        }
+    }
+}
+
+/// Extension trait for thread [`Builder`]s.
+///
+/// A builder can't tell the name it was given, so these methods take the
+/// name themselves, to log it if spawning fails.
+pub trait BuilderExt {
+    /// Names the thread `name` and spawns it, like [`Builder::spawn`],
+    /// yielding its handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to create the thread, logging the thread's name
+    /// and the I/O error kind to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn spawn_or_log<F, T>(self, name: impl Into<String>, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static;
+
+    /// Names the thread `name` and spawns it into `scope`, like
+    /// [`Builder::spawn_scoped`], yielding its handle.
+    ///
+    /// When `scope` was created by [`scope_or_log`], a panic of the thread
+    /// that isn't joined explicitly is logged, with the thread's name, when
+    /// the scope joins it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS fails to create the thread, logging the thread's name
+    /// and the I/O error kind to a [`tracing::Subscriber`] at an [`ERROR`]
+    /// level.
+    ///
+    /// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+    fn spawn_scoped_or_log<'scope, 'env, F, T>(
+        self,
+        scope: &'scope Scope<'scope, 'env>,
+        name: impl Into<String>,
+        f: F,
+    ) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope;
+}
+
+impl BuilderExt for Builder {
+    #[inline]
+    #[track_caller]
+    fn spawn_or_log<F, T>(self, name: impl Into<String>, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        callsite::register("Builder::spawn_or_log");
+        let name = name.into();
+        match self.name(name.clone()).spawn(f) {
+            Ok(handle) => handle,
+            Err(e) => spawn_failed(
+                "Builder::spawn_or_log",
+                "called `Builder::spawn_or_log()` and spawning failed",
+                &name,
+                &e,
+            ),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn spawn_scoped_or_log<'scope, 'env, F, T>(
+        self,
+        scope: &'scope Scope<'scope, 'env>,
+        name: impl Into<String>,
+        f: F,
+    ) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        callsite::register("Builder::spawn_scoped_or_log");
+        let name = name.into();
+        let scope_id = scope_id(scope);
+        let thread_name = name.clone();
+        let spawned =
+            self.name(name.clone()).spawn_scoped(scope, move || {
+                match panic::catch_unwind(AssertUnwindSafe(f)) {
+                    Ok(t) => t,
+                    Err(payload) => {
+                        scoped_thread_panicked(scope_id, &thread_name, &*payload);
+                        panic::resume_unwind(payload)
+                    }
+                }
+            });
+        match spawned {
+            Ok(handle) => handle,
+            Err(e) => spawn_failed(
+                "Builder::spawn_scoped_or_log",
+                "called `Builder::spawn_scoped_or_log()` and spawning failed",
+                &name,
+                &e,
+            ),
+        }
+    }
+}
+
+/// Creates a scope for spawning scoped threads, like [`thread::scope`],
+/// yielding the result of `f`.
+///
+/// # Panics
+///
+/// Panics if a thread spawned into the scope with
+/// [`spawn_scoped_or_log`](BuilderExt::spawn_scoped_or_log) panicked and
+/// wasn't joined explicitly, logging the names and the panic messages of all
+/// such threads to a [`tracing::Subscriber`] at an [`ERROR`] level, instead
+/// of only reporting that "a scoped thread panicked". Other panics, such as
+/// one of `f` itself, are propagated unchanged.
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[track_caller]
+pub fn scope_or_log<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    callsite::register("scope_or_log");
+    let mut id = None;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        thread::scope(|scope| {
+            let scope_id = scope_id(scope);
+            id = Some(scope_id);
+            SCOPES
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_or_insert_with(HashMap::new)
+                .insert(scope_id, Vec::new());
+            // A panic of `f` itself is propagated as is, so any panic of the
+            // scope comes from its threads.
+            panic::catch_unwind(AssertUnwindSafe(|| f(scope)))
+        })
+    }));
+    let panicked = id
+        .and_then(|id| {
+            SCOPES
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .as_mut()
+                .and_then(|scopes| scopes.remove(&id))
+        })
+        .unwrap_or_default();
+
+    match result {
+        Ok(Ok(t)) => t,
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(payload) if panicked.is_empty() => panic::resume_unwind(payload),
+        Err(_) => failed_with(
+            "scope_or_log",
+            "called `scope_or_log()` and a scoped thread panicked",
+            &format_args!("{}", panicked.join("; ")),
+        ),
+    }
+}
+
+fn scope_id(scope: &Scope<'_, '_>) -> usize {
+    scope as *const Scope<'_, '_> as usize
+}
+
+/// Notes the panic of a thread spawned into the scope `scope_id`, if that
+/// scope was created by [`scope_or_log`].
+fn scoped_thread_panicked(scope_id: usize, name: &str, payload: &(dyn Any + Send)) {
+    if let Some(panicked) = SCOPES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+        .and_then(|scopes| scopes.get_mut(&scope_id))
+    {
+        panicked.push(format!(
+            "thread {:?}, panic message {:?}",
+            name,
+            panic_message(payload)
+        ));
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn thread_panicked(
+    method: &'static str,
+    msg: &str,
+    name: Option<&str>,
+    payload: &(dyn Any + Send),
+) -> ! {
+    failed_with(
+        method,
+        msg,
+        &format_args!(
+            "thread {:?}, panic message {:?}",
+            name.unwrap_or("<unnamed>"),
+            panic_message(payload)
+        ),
+    )
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn spawn_failed(method: &'static str, msg: &str, name: &str, error: &io::Error) -> ! {
+    let value = format_args!(
+        "thread {:?}, error kind {:?}: {}",
+        name,
+        error.kind(),
+        error
+    );
+    fail(
+        &FailureRecord::new(method, msg, Some(&value), crate::facade::Level::ERROR, true)
+            .with_io_error(error),
+    )
+}
+
+/// Extracts the message of a panic from its payload, when it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else if let Some(failure) = payload.downcast_ref::<UnwrapFailure>() {
+        failure.to_string()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
@@ -0,0 +1,96 @@
+//! Timeouts for futures, on the tokio runtime.
+
+use crate::{callsite, emit, fail, recovery, span_capture, FailureRecord};
+use std::future::Future;
+use std::panic::Location;
+use std::time::Duration;
+use tokio::time::{error::Elapsed, Instant};
+
+/// Requires a future to complete within `duration`, yielding its output.
+///
+/// The location of this call is captured when the future is created, and
+/// reported for a failure even though it happens when the future is polled.
+///
+/// # Panics
+///
+/// Panics if `duration` elapses first, logging the operation `label`, the
+/// configured duration and the time actually elapsed to a
+/// [`tracing::Subscriber`] at an [`ERROR`] level.
+///
+/// [`ERROR`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.ERROR
+#[track_caller]
+pub fn timeout_or_log<'a, F>(
+    duration: Duration,
+    label: &'a str,
+    future: F,
+) -> impl Future<Output = F::Output> + 'a
+where
+    F: Future + 'a,
+{
+    callsite::register("timeout_or_log");
+    let location = Location::caller();
+    let span = span_capture::capture();
+    async move {
+        let started = Instant::now();
+        match tokio::time::timeout(duration, future).await {
+            Ok(output) => output,
+            Err(_) => {
+                let msg = format!("operation `{}` timed out after {:?}", label, duration);
+                let elapsed = started.elapsed();
+                let elapsed = format_args!("elapsed {:?}", elapsed);
+                let record = FailureRecord::new(
+                    "timeout_or_log",
+                    &msg,
+                    Some(&elapsed),
+                    crate::facade::Level::ERROR,
+                    true,
+                );
+                let _span = span_capture::enter(&span);
+                fail(&record.with_location(location))
+            }
+        }
+    }
+}
+
+/// Requires a future to complete within `duration`, like [`timeout_or_log`],
+/// but returns an error instead of panicking.
+///
+/// If `duration` elapses first, logs the operation `label`, the configured
+/// duration and the time actually elapsed to a [`tracing::Subscriber`] at a
+/// [`WARN`] level.
+///
+/// [`WARN`]: /tracing/0.1/tracing/struct.Level.html#associatedconstant.WARN
+#[track_caller]
+pub fn try_timeout_or_log<'a, F>(
+    duration: Duration,
+    label: &'a str,
+    future: F,
+) -> impl Future<Output = Result<F::Output, Elapsed>> + 'a
+where
+    F: Future + 'a,
+{
+    callsite::register("try_timeout_or_log");
+    let location = Location::caller();
+    let span = span_capture::capture();
+    async move {
+        let started = Instant::now();
+        let output = tokio::time::timeout(duration, future).await;
+        if output.is_ok() {
+            recovery::succeeded("try_timeout_or_log", location);
+        } else {
+            let msg = format!("operation `{}` timed out after {:?}", label, duration);
+            let elapsed = started.elapsed();
+            let elapsed = format_args!("elapsed {:?}", elapsed);
+            let record = FailureRecord::new(
+                "try_timeout_or_log",
+                &msg,
+                Some(&elapsed),
+                crate::facade::Level::WARN,
+                false,
+            );
+            let _span = span_capture::enter(&span);
+            emit(&record.with_location(location));
+        }
+        output
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use tracing_unwrap::AtomicExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn update_rejected() {
+    let permits = AtomicU8::new(0);
+    let result = std::panic::catch_unwind(|| {
+        permits.fetch_update_or_log(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `AtomicU8::fetch_update_or_log()` with an update that was rejected: observed value 0, after 1 attempts"
+    ));
+}
+
+#[test]
+fn update_accepted() {
+    let counter = AtomicUsize::new(5);
+    let previous = counter.fetch_update_or_log(Ordering::SeqCst, Ordering::SeqCst, |n| Some(n + 1));
+    assert_eq!((previous, counter.load(Ordering::SeqCst)), (5, 6));
+}
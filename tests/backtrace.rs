@@ -0,0 +1,39 @@
+#![cfg(feature = "backtrace")]
+
+use tracing_unwrap::ResultExt;
+
+fn discard() {
+    Result::<(), _>::Err("lost").ok_or_log();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn trimmed_backtrace() {
+    discard();
+
+    // The first frame is the caller of the failed method.
+    assert!(logs_contain(
+        "unwrap.stacktrace=\"   0: backtrace::discard\\n"
+    ));
+    assert!(!logs_contain("tracing_unwrap::"));
+}
+
+fn discard_with_dispatch() {
+    Result::<(), _>::Err("redirected").ok_or_log();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn trimmed_backtrace_with_dispatch() {
+    // Routes the event through `tracing`'s scoped dispatcher, whose frames
+    // sit between the crate's frames.
+    let dispatch = tracing::dispatcher::get_default(|dispatch| dispatch.clone());
+    tracing_unwrap::set_dispatch(Some(dispatch));
+    discard_with_dispatch();
+    tracing_unwrap::set_dispatch(None);
+
+    assert!(logs_contain(
+        "unwrap.stacktrace=\"   0: backtrace::discard_with_dispatch\\n"
+    ));
+    assert!(!logs_contain("tracing_core::"));
+}
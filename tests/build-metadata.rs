@@ -0,0 +1,20 @@
+#![cfg(feature = "build-metadata")]
+
+use tracing_unwrap::{BuildMetadata, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn stamped_onto_events() {
+    tracing_unwrap::set_build_metadata(
+        BuildMetadata::new()
+            .with_commit("9fceb02")
+            .with_profile("release")
+            .with_timestamp("2026-10-16T12:00:00Z"),
+    );
+
+    Err::<(), _>("lost").ok_or_log();
+
+    assert!(logs_contain(
+        "unwrap.build.commit=\"9fceb02\" unwrap.build.profile=\"release\" unwrap.build.timestamp=\"2026-10-16T12:00:00Z\""
+    ));
+}
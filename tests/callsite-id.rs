@@ -0,0 +1,21 @@
+use tracing_unwrap::ResultExt;
+
+fn discard(result: Result<(), &str>) {
+    result.ok_or_log();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn callsite_id() {
+    discard(Err("first"));
+    discard(Err("second"));
+
+    // The identifier is a fixed hash of the file, the line and the method, so
+    // its value must never change.
+    for value in ["first", "second"] {
+        assert!(logs_contain(&format!(
+            "{:?} unwrap.callsite_id=da5e9b883b596dc0 unwrap.method=\"Result::ok_or_log\"",
+            value
+        )));
+    }
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "callsite-inventory")]
+
+use tracing_unwrap::{guard_or_log, OptionExt, ResultExt};
+
+fn checked(value: u8) -> Option<u8> {
+    guard_or_log!(value > 0, None);
+    Some(value)
+}
+
+#[test]
+fn lists_callsites_that_ran() {
+    Some(1).unwrap_or_log();
+    Ok::<_, ()>(2).expect_or_log("two");
+    checked(3);
+
+    let callsites = tracing_unwrap::callsites();
+    let ours: Vec<_> = callsites
+        .iter()
+        .filter(|c| c.location().file() == "tests/callsite-inventory.rs")
+        .map(|c| (c.location().line(), c.method()))
+        .collect();
+
+    assert_eq!(
+        ours,
+        [
+            (6, "guard_or_log!"),
+            (12, "Option::unwrap_or_log"),
+            (13, "Result::expect_or_log"),
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "tokio")]
+
+use futures::FutureExt;
+use tokio::sync::{broadcast, mpsc, watch};
+use tracing_unwrap::{BroadcastReceiverExt, MpscSenderExt, WatchReceiverExt};
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn broadcast_lagged() {
+    let (tx, mut rx) = broadcast::channel(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(rx.recv_or_log().await, Some(3));
+    assert_eq!(rx.recv_or_log().await, Some(4));
+    assert_eq!(rx.recv_or_log().await, None);
+    assert!(logs_contain(
+        "receiver lagged behind the `broadcast` channel: skipped 3 messages"
+    ));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn watch_closed() {
+    let (tx, mut rx) = watch::channel(0);
+    tx.send(1).unwrap();
+    assert!(rx.changed_or_log().await);
+    drop(tx);
+    assert!(!rx.changed_or_log().await);
+    assert!(logs_contain(
+        "called `watch::Receiver::changed_or_log()` after the sender was dropped"
+    ));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn mpsc_full_and_closed() {
+    let (tx, rx) = mpsc::channel(1);
+    tx.send_or_log(1).await;
+    assert_eq!(tx.try_send_or_log(2), Err(2));
+    drop(rx);
+    assert_eq!(tx.try_send_or_log(3), Err(3));
+    let send = std::panic::AssertUnwindSafe(tx.send_or_log(4)).catch_unwind();
+    assert!(send.await.is_err());
+
+    assert!(logs_contain(
+        "WARN mpsc_full_and_closed: tracing_unwrap: called `mpsc::Sender::try_send_or_log()` on a full channel: capacity 1"
+    ));
+    assert!(logs_contain(
+        "ERROR mpsc_full_and_closed: tracing_unwrap: called `mpsc::Sender::try_send_or_log()` after the receiver was dropped"
+    ));
+    assert!(logs_contain(
+        "called `mpsc::Sender::send_or_log()` after the receiver was dropped"
+    ));
+}
@@ -0,0 +1,20 @@
+use tracing_unwrap::{CharExt, U32CharExt};
+
+#[test]
+fn conversions() {
+    assert_eq!('f'.to_digit_or_log(16), 15);
+    assert_eq!('A'.to_ascii_or_log(), b'A');
+    assert_eq!(7.to_digit_char_or_log(10), '7');
+    assert_eq!(0x41.to_char_or_log(), 'A');
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn not_a_digit() {
+    let result = std::panic::catch_unwind(|| {
+        'g'.to_digit_or_log(16);
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("char 'g', radix 16"));
+}
@@ -0,0 +1,97 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing_unwrap::{CircuitBreaker, ResultExt};
+
+/// Serializes the tests, which install process-wide policies.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn discard(result: Result<(), &str>) {
+    result.ok_or_log();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn trips_to_panic() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let breaker = CircuitBreaker::panic_after(3, Duration::from_secs(60)).per_callsite();
+    tracing_unwrap::set_circuit_breaker(Some(breaker));
+
+    discard(Err("first"));
+    discard(Err("second"));
+    // Another callsite is counted separately.
+    Err::<(), _>("elsewhere").ok_or_log();
+    let third = std::panic::catch_unwind(|| discard(Err("third")));
+    tracing_unwrap::set_circuit_breaker(None);
+
+    assert!(third.is_err());
+    assert!(logs_contain(
+        "circuit breaker tripped: 3 failures within 60s"
+    ));
+    assert!(logs_contain("\"third\""));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn window_expires() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use tracing_unwrap::Clock;
+
+    /// A clock that only moves when told to.
+    struct ManualClock(Arc<AtomicU64>);
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Duration {
+            Duration::from_secs(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    let seconds = Arc::new(AtomicU64::new(0));
+    tracing_unwrap::set_clock(Some(Box::new(ManualClock(Arc::clone(&seconds)))));
+    let breaker = CircuitBreaker::panic_after(2, Duration::from_secs(10)).per_callsite();
+    tracing_unwrap::set_circuit_breaker(Some(breaker));
+
+    // The failures are further apart than the window, so the breaker holds.
+    for _ in 0..3 {
+        discard(Err("spaced out"));
+        seconds.fetch_add(11, Ordering::SeqCst);
+    }
+    tracing_unwrap::set_circuit_breaker(None);
+    tracing_unwrap::set_clock(None);
+
+    assert!(!logs_contain("circuit breaker tripped"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn trip_is_filtered() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let breaker = CircuitBreaker::panic_after(1, Duration::from_secs(60));
+    tracing_unwrap::set_circuit_breaker(Some(breaker));
+
+    tracing_unwrap::set_enabled(false);
+    let first = std::panic::catch_unwind(|| discard(Err("silenced")));
+    tracing_unwrap::set_enabled(true);
+    tracing_unwrap::set_circuit_breaker(None);
+
+    assert!(first.is_err());
+    assert!(!logs_contain("circuit breaker tripped"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn zero_threshold() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    // A threshold of 0 trips on the first failure, as 1 does.
+    let breaker = CircuitBreaker::panic_after(0, Duration::from_secs(60));
+    tracing_unwrap::set_circuit_breaker(Some(breaker));
+
+    let first = std::panic::catch_unwind(|| discard(Err("first")));
+    tracing_unwrap::set_circuit_breaker(None);
+
+    assert!(first.is_err());
+    assert!(logs_contain(
+        "circuit breaker tripped: 1 failures within 60s"
+    ));
+}
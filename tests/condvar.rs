@@ -0,0 +1,35 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+use tracing_unwrap::CondvarExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn wait_timed_out() {
+    let mutex = Mutex::new(());
+    let condvar = Condvar::new();
+
+    let (_guard, result) =
+        condvar.wait_timeout_or_log(mutex.lock().unwrap(), Duration::from_millis(10));
+
+    assert!(result.timed_out());
+    assert!(logs_contain(
+        "WARN wait_timed_out: tracing_unwrap: wait timed out after 10ms: waited"
+    ));
+}
+
+#[test]
+fn notified() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let notifier = Arc::clone(&pair);
+    thread::spawn(move || {
+        *notifier.0.lock().unwrap() = true;
+        notifier.1.notify_one();
+    });
+
+    let (mutex, condvar) = &*pair;
+    let mut ready = mutex.lock().unwrap();
+    while !*ready {
+        ready = condvar.wait_or_log(ready);
+    }
+}
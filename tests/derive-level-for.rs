@@ -0,0 +1,31 @@
+#![cfg(feature = "derive")]
+
+use tracing::Level;
+use tracing_unwrap::LevelFor;
+
+#[allow(dead_code)]
+#[derive(Debug, LevelFor)]
+#[level(warn)]
+enum FetchError<T> {
+    #[level(debug)]
+    CacheMiss,
+    Timeout(T),
+    #[level(error)]
+    Corrupt {
+        offset: u64,
+    },
+}
+
+#[derive(Debug, LevelFor)]
+struct Unannotated;
+
+#[test]
+fn derive_level_for() {
+    assert_eq!(FetchError::<u32>::CacheMiss.level(), Level::DEBUG);
+    assert_eq!(FetchError::Timeout(30).level(), Level::WARN);
+    assert_eq!(
+        FetchError::<u32>::Corrupt { offset: 7 }.level(),
+        Level::ERROR
+    );
+    assert_eq!(Unannotated.level(), Level::ERROR);
+}
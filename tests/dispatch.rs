@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Dispatch, Event, Metadata, Subscriber};
+use tracing_unwrap::ResultExt;
+
+/// Counts the events it receives.
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Subscriber for Counter {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, _: &Event<'_>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn dispatch() {
+    let host = Counter::default();
+    let plugin = Counter::default();
+    let _default = tracing::subscriber::set_default(host.clone());
+
+    Result::<(), _>::Err("to host").ok_or_log();
+    tracing_unwrap::set_dispatch(Some(Dispatch::new(plugin.clone())));
+    Result::<(), _>::Err("to plugin").ok_or_log();
+    tracing_unwrap::set_dispatch(None);
+    Result::<(), _>::Err("to host").ok_or_log();
+
+    assert_eq!(host.0.load(Ordering::SeqCst), 2);
+    assert_eq!(plugin.0.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt;
+use tracing_unwrap::BoxErrorExt;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[test]
+#[tracing_test::traced_test]
+fn downcast_mismatch() {
+    let result = std::panic::catch_unwind(|| {
+        let boxed: Box<dyn Error + Send + Sync> = Box::new(Timeout);
+        boxed.downcast_or_log::<fmt::Error>();
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "expected `core::fmt::Error`, found operation timed out (Timeout)"
+    ));
+}
+
+#[test]
+fn downcast_match() {
+    let boxed: Box<dyn Error> = Box::new(Timeout);
+    boxed.downcast_ref_or_log::<Timeout>();
+    boxed.downcast_or_log::<Timeout>();
+}
+
+/// An error with an endless chain of sources.
+#[derive(Debug)]
+struct Cyclic;
+
+impl fmt::Display for Cyclic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cyclic")
+    }
+}
+
+impl Error for Cyclic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&Cyclic)
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn downcast_source_chain() {
+    tracing_unwrap::set_max_source_depth(3);
+    let result = std::panic::catch_unwind(|| {
+        let boxed: Box<dyn Error> = Box::new(Cyclic);
+        boxed.downcast_ref_or_log::<Timeout>();
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "unwrap.sources=\"cyclic: cyclic: cyclic\" unwrap.sources_truncated=3"
+    ));
+}
@@ -0,0 +1,37 @@
+use std::fmt;
+use tracing_unwrap::ErrorResultExt;
+
+#[derive(Debug)]
+struct ConfigError(std::io::Error);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("config unreadable")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn load() -> Result<(), ConfigError> {
+    Err(ConfigError(std::io::Error::other("disk offline")))
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn logged_with_sources() {
+    assert_eq!(load().ok_or_log_error(), None);
+    assert!(logs_contain(
+        "called `Result::ok_or_log_error` on an `Err` value: ConfigError(Custom"
+    ));
+    assert!(logs_contain("unwrap.sources=\"disk offline\""));
+
+    let result = std::panic::catch_unwind(|| load().expect_or_log_error("load config"));
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "ERROR logged_with_sources: tracing_unwrap: load config: ConfigError"
+    ));
+}
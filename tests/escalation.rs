@@ -0,0 +1,28 @@
+use tracing::Level;
+use tracing_unwrap::{Escalation, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn escalates_after_threshold() {
+    tracing_unwrap::set_escalation(Some(Escalation::after(3).with_threshold(1, Level::INFO)));
+
+    for attempt in 1..=5 {
+        Result::<(), _>::Err(attempt).ok_or_log();
+    }
+
+    assert!(logs_contain("INFO escalates_after_threshold: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: 1"));
+    assert!(logs_contain("WARN escalates_after_threshold: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: 3"));
+    assert!(logs_contain("ERROR escalates_after_threshold: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: 4"));
+}
+
+#[test]
+fn level_for_occurrence() {
+    let policy = Escalation::new(Level::ERROR)
+        .with_threshold(10, Level::WARN)
+        .with_threshold(3, Level::DEBUG);
+
+    assert_eq!(policy.level_for(1), Level::DEBUG);
+    assert_eq!(policy.level_for(3), Level::DEBUG);
+    assert_eq!(policy.level_for(4), Level::WARN);
+    assert_eq!(policy.level_for(11), Level::ERROR);
+}
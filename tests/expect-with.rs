@@ -0,0 +1,41 @@
+use tracing_unwrap::{OptionExt, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn message_built_on_failure() {
+    let result = std::panic::catch_unwind(|| {
+        Err::<(), _>(404).expect_or_log_with(|status| format!("fetch user 7 ({})", status))
+    });
+    assert!(result.is_err());
+
+    let result =
+        std::panic::catch_unwind(|| None::<()>.expect_or_log_with(|| format!("user {}", 7)));
+    assert!(result.is_err());
+
+    let result = std::panic::catch_unwind(|| {
+        Some(3).expect_none_or_log_with(|n| format!("{} stale sessions", n))
+    });
+    assert!(result.is_err());
+
+    let result = std::panic::catch_unwind(|| {
+        Ok::<_, ()>("cached").expect_err_or_log_with(|v| format!("expected a miss, got {}", v))
+    });
+    assert!(result.is_err());
+
+    assert!(logs_contain("fetch user 7 (404): 404"));
+    assert!(logs_contain("tracing_unwrap: user 7"));
+    assert!(logs_contain("3 stale sessions: 3"));
+    assert!(logs_contain("expected a miss, got cached: \"cached\""));
+}
+
+#[test]
+fn message_not_built_on_success() {
+    let value = Ok::<_, ()>(1).expect_or_log_with(|_| unreachable!());
+    assert_eq!(value, 1);
+    assert_eq!(Some(2).expect_or_log_with(|| unreachable!()), 2);
+    None::<()>.expect_none_or_log_with(|_| unreachable!());
+    assert_eq!(
+        Err::<(), _>(3).expect_err_or_log_with(|_| unreachable!()),
+        3
+    );
+}
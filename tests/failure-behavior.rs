@@ -0,0 +1,45 @@
+use std::process::Command;
+use tracing_unwrap::{FailureBehavior, OptionExt};
+
+/// Runs `test` again in a child process that fails with `behavior`.
+fn run_child(test: &str, behavior: &str) -> std::process::ExitStatus {
+    Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", test, "--test-threads=1"])
+        .env("FAILURE_BEHAVIOR", behavior)
+        .output()
+        .unwrap()
+        .status
+}
+
+/// Fails with the behavior passed by [`run_child`], if in the child process.
+fn fail_in_child() {
+    let behavior = match std::env::var("FAILURE_BEHAVIOR").as_deref() {
+        Ok("abort") => FailureBehavior::Abort,
+        Ok("exit") => FailureBehavior::Exit(3),
+        _ => return,
+    };
+    tracing_unwrap::set_failure_behavior(behavior);
+    Option::<()>::None.unwrap_or_log();
+    unreachable!()
+}
+
+#[test]
+fn exits() {
+    fail_in_child();
+    assert_eq!(run_child("exits", "exit").code(), Some(3));
+}
+
+#[test]
+fn aborts() {
+    fail_in_child();
+    let status = run_child("aborts", "abort");
+    assert!(!status.success());
+    // The test harness exits with 101 when a test panics.
+    assert_ne!(status.code(), Some(101));
+}
+
+#[test]
+fn panics_by_default() {
+    let result = std::panic::catch_unwind(|| Option::<()>::None.unwrap_or_log());
+    assert!(result.is_err());
+}
@@ -0,0 +1,35 @@
+use std::sync::Mutex;
+use tracing_unwrap::{FailureRecord, OptionExt, ResultExt};
+
+static SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_failure(record: &FailureRecord<'_>) {
+    let entry = format!("{} (fatal: {})", record, record.is_fatal());
+    SEEN.lock().unwrap().push(entry);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn failure_hook() {
+    tracing_unwrap::set_failure_hook(Some(record_failure));
+    Result::<(), _>::Err("discarded").ok_or_log();
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("fatal"));
+
+    // Filtered-out failures still run the hook.
+    tracing_unwrap::set_enabled(false);
+    Result::<(), _>::Err("filtered").ok_or_log();
+    tracing_unwrap::set_enabled(true);
+
+    tracing_unwrap::set_failure_hook(None);
+    Result::<(), _>::Err("unhooked").ok_or_log();
+
+    assert_eq!(
+        *SEEN.lock().unwrap(),
+        [
+            "called `Result::ok_or_log` on an `Err` value: \"discarded\" (fatal: false)",
+            "fatal (fatal: true)",
+            "called `Result::ok_or_log` on an `Err` value: \"filtered\" (fatal: false)",
+        ]
+    );
+    assert!(logs_contain("fatal"));
+}
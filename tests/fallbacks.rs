@@ -0,0 +1,42 @@
+use tracing_unwrap::{OptionExt, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn err_fallbacks() {
+    assert_eq!(Err::<u32, _>("no port").unwrap_or_log_or(8080), 8080);
+    assert_eq!(
+        Err::<usize, _>("bad size").unwrap_or_else_or_log(|e| e.len()),
+        8
+    );
+    assert_eq!(Err::<Vec<u8>, _>("no body").unwrap_or_default_or_log(), []);
+    assert_eq!(Ok::<_, &str>(443).unwrap_or_log_or(8080), 443);
+
+    assert!(logs_contain(
+        "ERROR err_fallbacks: tracing_unwrap: called `Result::unwrap_or_log_or()` on an `Err` value: \"no port\""
+    ));
+    assert!(logs_contain(
+        "called `Result::unwrap_or_else_or_log()` on an `Err` value: \"bad size\""
+    ));
+    assert!(logs_contain(
+        "called `Result::unwrap_or_default_or_log()` on an `Err` value: \"no body\""
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn none_fallbacks() {
+    assert_eq!(None.unwrap_or_log_or(3), 3);
+    assert_eq!(None.unwrap_or_else_or_log(|| 4), 4);
+    assert_eq!(None::<String>.unwrap_or_default_or_log(), "");
+    assert_eq!(Some(1).unwrap_or_default_or_log(), 1);
+
+    assert!(logs_contain(
+        "ERROR none_fallbacks: tracing_unwrap: called `Option::unwrap_or_log_or()` on a `None` value"
+    ));
+    assert!(logs_contain(
+        "called `Option::unwrap_or_else_or_log()` on a `None` value"
+    ));
+    assert!(logs_contain(
+        "called `Option::unwrap_or_default_or_log()` on a `None` value"
+    ));
+}
@@ -0,0 +1,24 @@
+use tracing_unwrap::{OptionExt, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn filter_by_location() {
+    tracing_unwrap::set_filter("tests=off,tests/filter.rs=error".parse().unwrap());
+    Result::<(), _>::Err("discarded").ok_or_log();
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("emitted"));
+
+    tracing_unwrap::set_filter("tests=off".parse().unwrap());
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("filtered"));
+
+    assert!(!logs_contain("discarded"));
+    assert!(logs_contain("emitted"));
+    assert!(!logs_contain("filtered"));
+}
+
+#[test]
+fn parse_errors() {
+    assert!("src=loud".parse::<tracing_unwrap::Filter>().is_err());
+    assert!(" warn , src/db.rs = off ,"
+        .parse::<tracing_unwrap::Filter>()
+        .is_ok());
+}
@@ -0,0 +1,45 @@
+use std::ops::ControlFlow;
+use std::panic::catch_unwind;
+use std::task::Poll;
+use tracing_unwrap::{ControlFlowExt, PollExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn control_flow() {
+    assert_eq!(ControlFlow::<&str, _>::Continue(1).continue_or_log(), 1);
+    assert_eq!(ControlFlow::<_, ()>::Break(2).break_or_log(), 2);
+
+    assert!(catch_unwind(|| ControlFlow::<_, ()>::Break("stop").continue_or_log()).is_err());
+    assert!(catch_unwind(|| ControlFlow::<(), _>::Continue(3).break_or_log()).is_err());
+
+    assert!(logs_contain(
+        "called `ControlFlow::continue_or_log()` on a `Break` value: \"stop\""
+    ));
+    assert!(logs_contain(
+        "called `ControlFlow::break_or_log()` on a `Continue` value: 3"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn poll() {
+    assert_eq!(
+        Poll::<Result<u8, &str>>::Pending.unwrap_ready_or_log(),
+        Poll::Pending
+    );
+    assert_eq!(
+        Poll::Ready(Ok::<_, &str>(1)).unwrap_ready_or_log(),
+        Poll::Ready(1)
+    );
+    assert!(catch_unwind(|| Poll::Ready(Err::<(), _>("reset")).unwrap_ready_or_log()).is_err());
+
+    let polled = Poll::Ready(Err::<(), _>("timed out")).inspect_err_or_log();
+    assert_eq!(polled, Poll::Ready(Err("timed out")));
+
+    assert!(logs_contain(
+        "ERROR poll: tracing_unwrap: called `Poll::unwrap_ready_or_log()` on a `Ready(Err)` value: \"reset\""
+    ));
+    assert!(logs_contain(
+        "WARN poll: tracing_unwrap: called `Poll::inspect_err_or_log()` on a `Ready(Err)` value: \"timed out\""
+    ));
+}
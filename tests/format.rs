@@ -0,0 +1,27 @@
+use tracing_unwrap::{FailureRecord, OptionExt, ResultExt};
+
+fn house_style(record: &FailureRecord<'_>) -> String {
+    format!("[house] {} (fatal: {})", record, record.is_fatal())
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn formatters() {
+    tracing_unwrap::set_event_formatter(Some(house_style));
+    tracing_unwrap::set_panic_formatter(Some(|record| {
+        format!("custom panic: {}", record.message())
+    }));
+
+    Result::<(), _>::Err("flaky").ok_or_log();
+    let payload =
+        std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("gone")).unwrap_err();
+
+    assert!(logs_contain(
+        "[house] called `Result::ok_or_log` on an `Err` value: \"flaky\" (fatal: false)"
+    ));
+    assert!(logs_contain("[house] gone (fatal: true)"));
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "custom panic: gone"
+    );
+}
@@ -0,0 +1,46 @@
+use futures::executor::block_on;
+use futures::future::{ready, FutureExt};
+use std::panic::AssertUnwindSafe;
+use std::sync::Mutex;
+use tracing_unwrap::{
+    FailureRecord, FailureReporter, OptionFutureExt, ResultFutureExt, TracingReporter,
+};
+
+static LINES: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+/// Records the line of each failure, to tell where it was reported.
+struct Lines;
+
+impl FailureReporter for Lines {
+    fn report(&self, record: &FailureRecord<'_>) {
+        LINES.lock().unwrap().push(record.location().line());
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn future_adapters() {
+    tracing_unwrap::set_reporter(Some(Box::new((TracingReporter, Lines))));
+
+    assert_eq!(block_on(ready(Ok::<_, &str>(1)).unwrap_or_log()), 1);
+    assert_eq!(block_on(ready(Some(2)).expect_or_log("missing")), 2);
+    assert_eq!(block_on(ready(Err::<(), _>("gone")).ok_or_log()), None);
+
+    // The failures are reported where the adapters were created, not where
+    // the futures are polled.
+    let line = line!() + 1;
+    let unwrap = ready(Err::<(), _>("broken")).map(|r| r).unwrap_or_log();
+    let expect = ready(None::<()>).expect_or_log("no value");
+    assert!(block_on(AssertUnwindSafe(unwrap).catch_unwind()).is_err());
+    assert!(block_on(AssertUnwindSafe(expect).catch_unwind()).is_err());
+    tracing_unwrap::set_reporter(None);
+
+    assert!(logs_contain(
+        "called `Result::ok_or_log` on an `Err` value: \"gone\""
+    ));
+    assert!(logs_contain(
+        "called `Result::unwrap_or_log()` on an `Err` value: \"broken\""
+    ));
+    assert!(logs_contain("no value"));
+    assert_eq!(LINES.lock().unwrap()[1..], [line, line + 1]);
+}
@@ -0,0 +1,30 @@
+use tracing::Level;
+use tracing_unwrap::guard_or_log;
+
+fn checked_div(a: u32, b: u32) -> Option<u32> {
+    guard_or_log!(b != 0, None);
+    Some(a / b)
+}
+
+fn apply(version: u32, applied: &mut bool) {
+    guard_or_log!(level: Level::ERROR, version >= 2);
+    *applied = true;
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn guards() {
+    assert_eq!(checked_div(6, 3), Some(2));
+    assert_eq!(checked_div(6, 0), None);
+
+    let mut applied = false;
+    apply(1, &mut applied);
+    assert!(!applied);
+
+    assert!(logs_contain(
+        "WARN guards: tracing_unwrap: guard `b != 0` failed unwrap.details={condition: \"b != 0\"}"
+    ));
+    assert!(logs_contain(
+        "ERROR guards: tracing_unwrap: guard `version >= 2` failed"
+    ));
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "heapless")]
+
+use tracing_unwrap::{HeaplessIndexMapExt, HeaplessVecExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn push_past_capacity() {
+    let result = std::panic::catch_unwind(|| {
+        let mut vec = heapless::Vec::<u8, 2>::new();
+        vec.push_or_log(1);
+        vec.push_or_log(2);
+        vec.push_or_log(3);
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("capacity 2, attempted length 3"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn insert_past_capacity() {
+    let result = std::panic::catch_unwind(|| {
+        let mut map = heapless::index_map::FnvIndexMap::<u8, u8, 2>::new();
+        assert_eq!(map.insert_or_log(1, 1), None);
+        assert_eq!(map.insert_or_log(1, 2), Some(1));
+        map.insert_or_log(2, 2);
+        map.insert_or_log(3, 3);
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("capacity 2, attempted length 3"));
+}
@@ -0,0 +1,29 @@
+use tracing_unwrap::ResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn instance_id() {
+    tracing_unwrap::set_instance_id(Some("worker-7".into()));
+    Err::<(), _>("identified").ok_or_log();
+    tracing_unwrap::set_instance_id(None);
+    Err::<(), _>("anonymous").ok_or_log();
+
+    logs_assert(|lines: &[&str]| {
+        let tagged = |value: &str| {
+            let line = lines.iter().find(|line| line.contains(value)).unwrap();
+            line.contains("unwrap.instance_id=\"worker-7\"")
+        };
+        match (tagged("identified"), tagged("anonymous")) {
+            (true, false) => Ok(()),
+            tags => Err(format!("unexpected instance ids: {:?}", tags)),
+        }
+    });
+
+    #[cfg(feature = "hostname")]
+    {
+        tracing_unwrap::set_instance_id_from_hostname();
+        Err::<(), _>("on this host").ok_or_log();
+        tracing_unwrap::set_instance_id(None);
+        assert!(logs_contain("unwrap.instance_id="));
+    }
+}
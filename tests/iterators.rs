@@ -0,0 +1,17 @@
+use tracing_unwrap::ResultIteratorExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn errs_pass_through() {
+    let items: Vec<_> = ["1", "x", "3"]
+        .iter()
+        .map(|s| s.parse::<u8>())
+        .inspect_errs_or_log()
+        .collect();
+
+    assert_eq!(items.len(), 3);
+    assert!(items[1].is_err());
+    assert!(logs_contain(
+        "item 1 of `inspect_errs_or_log()` is an `Err` value: ParseIntError { kind: InvalidDigit }"
+    ));
+}
@@ -0,0 +1,23 @@
+use std::panic::AssertUnwindSafe;
+use std::thread;
+use tracing_unwrap::JoinHandleExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_thread() {
+    let handle = thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("disk full"))
+        .unwrap();
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| handle.join_or_log()));
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `JoinHandle::join_or_log()` on a thread that panicked: thread \"worker\", panic message \"disk full\""
+    ));
+}
+
+#[test]
+fn thread_finished() {
+    assert_eq!(thread::spawn(|| 5).join_or_log(), 5);
+}
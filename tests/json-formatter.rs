@@ -0,0 +1,18 @@
+use tracing_unwrap::ResultExt;
+
+#[test]
+fn json_panic_message() {
+    tracing_unwrap::set_panic_formatter(Some(tracing_unwrap::json_formatter));
+
+    let payload =
+        std::panic::catch_unwind(|| Err::<(), _>("line\n\"quoted\"").expect_or_log("load config"))
+            .unwrap_err();
+
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with(concat!(
+        r#"{"msg":"load config: \"line\\n\\\"quoted\\\"\"","#,
+        r#""type":"Result::expect_or_log","location":"tests/json-formatter.rs:8:70","#,
+        r#""callsite_id":""#,
+    )));
+    assert!(message.ends_with("\"}"));
+}
@@ -0,0 +1,16 @@
+use tracing_unwrap::ResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn kill_switch() {
+    tracing_unwrap::set_enabled(false);
+    Err::<(), _>("silenced").ok_or_log();
+    let fatal = std::panic::catch_unwind(|| Err::<(), _>("silenced too").unwrap_or_log());
+    tracing_unwrap::set_enabled(true);
+    Err::<(), _>("audible").ok_or_log();
+
+    // Failures still panic while events are disabled.
+    assert!(fatal.is_err());
+    assert!(!logs_contain("silenced"));
+    assert!(logs_contain("audible"));
+}
@@ -0,0 +1,45 @@
+use tracing_unwrap::{let_ok_or_log, let_some_or_log};
+
+fn parse_port(input: &str) -> Option<u16> {
+    let_ok_or_log!(let Ok(port) = input.parse::<u16>(), "invalid port"; else return None);
+    Some(port)
+}
+
+fn find_even(values: &[u32]) -> Option<u32> {
+    let_some_or_log!(let Some(&even @ 0..=9) = values.iter().find(|v| *v % 2 == 0), "no small even value"; else return None);
+    Some(even)
+}
+
+#[test]
+fn bindings() {
+    let_ok_or_log!(let Ok((a, b)) = Ok::<_, ()>((1, 2)));
+    let_some_or_log!(let Some(name) = Some("tracing"), "no name");
+    assert_eq!((a, b, name), (1, 2, "tracing"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn early_return() {
+    assert_eq!(parse_port("8080"), Some(8080));
+    assert_eq!(parse_port("eighty"), None);
+    assert_eq!(find_even(&[1, 12]), None);
+    assert_eq!(find_even(&[1, 4]), Some(4));
+
+    assert!(logs_contain(
+        "WARN early_return: tracing_unwrap: invalid port: ParseIntError"
+    ));
+    assert!(logs_contain(
+        "WARN early_return: tracing_unwrap: no small even value"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn fatal() {
+    let result = std::panic::catch_unwind(|| {
+        let_ok_or_log!(let Ok(Some(_value)) = Ok::<Option<u8>, ()>(None), "no value");
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("ERROR fatal: tracing_unwrap: no value"));
+}
@@ -0,0 +1,32 @@
+use tracing::Level;
+use tracing_unwrap::{OptionExt, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn discarded_at_level() {
+    assert_eq!(Err::<(), _>("cache miss").ok_or_log_at(Level::DEBUG), None);
+    assert_eq!(Ok::<_, &str>(3).ok_or_log_at(Level::DEBUG), Some(3));
+
+    assert!(logs_contain(
+        "DEBUG discarded_at_level: tracing_unwrap: called `Result::ok_or_log_at` on an `Err` value: \"cache miss\""
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn fatal_at_level() {
+    let result = std::panic::catch_unwind(|| {
+        Err::<(), _>("stale lease").expect_or_log_at(Level::WARN, "renew lease")
+    });
+    assert!(result.is_err());
+
+    let result = std::panic::catch_unwind(|| None::<()>.unwrap_or_log_at(Level::INFO));
+    assert!(result.is_err());
+
+    assert!(logs_contain(
+        "WARN fatal_at_level: tracing_unwrap: renew lease: \"stale lease\""
+    ));
+    assert!(logs_contain(
+        "INFO fatal_at_level: tracing_unwrap: called `Option::unwrap_or_log_at()` on a `None` value"
+    ));
+}
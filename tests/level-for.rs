@@ -0,0 +1,29 @@
+use std::io;
+use tracing_unwrap::LeveledResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn io_error_levels() {
+    Err::<(), _>(io::Error::from(io::ErrorKind::NotFound)).ok_or_log_leveled();
+    Err::<(), _>(io::Error::from(io::ErrorKind::PermissionDenied)).ok_or_log_leveled();
+
+    assert!(logs_contain(
+        "WARN io_error_levels: tracing_unwrap: called `Result::ok_or_log_leveled` on an `Err` value: Kind(NotFound)"
+    ));
+    assert!(logs_contain(
+        "ERROR io_error_levels: tracing_unwrap: called `Result::ok_or_log_leveled` on an `Err` value: Kind(PermissionDenied)"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn fatal_at_declared_level() {
+    let result = std::panic::catch_unwind(|| {
+        Err::<(), _>(io::ErrorKind::NotFound).expect_or_log_leveled("open config")
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "WARN fatal_at_declared_level: tracing_unwrap: open config: NotFound"
+    ));
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "attributes")]
+
+use std::error::Error;
+use std::fmt;
+use tracing_unwrap::{unwrap_context, ErrorResultExt, LogBuffer};
+
+#[derive(Debug)]
+struct Rejected(Timeout);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("rejected")
+    }
+}
+
+impl Error for Rejected {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[unwrap_context("saving")]
+fn save() {
+    let span = tracing::info_span!("request", id = 7);
+    let _entered = span.enter();
+    Err::<(), _>(Rejected(Timeout)).ok_or_log_error();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn flushed_as_logged() {
+    // The same failure, buffered and flushed once the span and context it
+    // happened in have been left, then logged directly.
+    for buffered in [true, false] {
+        let buffer = buffered.then(LogBuffer::start);
+        save();
+        if let Some(buffer) = buffer {
+            buffer.flush();
+        }
+    }
+
+    logs_assert(|lines: &[&str]| {
+        // Everything but the time and the number of occurrences is the same.
+        let events: Vec<String> = lines
+            .iter()
+            .filter(|line| line.contains("ok_or_log_error"))
+            .map(|line| {
+                line.split(' ')
+                    .skip(1)
+                    .filter(|field| !field.starts_with("unwrap.occurrence="))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        match events.as_slice() {
+            [flushed, logged]
+                if flushed == logged
+                    && logged.contains("request{id=7}")
+                    && logged.contains("saving")
+                    && logged.contains("unwrap.sources=\"timed out\"") =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("unexpected events: {:#?}", events)),
+        }
+    });
+}
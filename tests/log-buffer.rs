@@ -0,0 +1,33 @@
+use tracing_unwrap::{LogBuffer, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn log_buffer() {
+    let buffer = LogBuffer::start();
+    Err::<(), _>("transient").ok_or_log();
+    assert_eq!(buffer.len(), 1);
+    buffer.discard();
+    assert!(!logs_contain("transient"));
+
+    let outer = LogBuffer::start();
+    let inner = LogBuffer::start();
+    Err::<(), _>("first attempt").ok_or_log();
+    inner.flush();
+    assert!(!logs_contain("first attempt"));
+    assert_eq!(outer.len(), 1);
+    Err::<(), _>("second attempt").ok_or_log();
+    outer.flush();
+
+    Err::<(), _>("unbuffered").ok_or_log();
+
+    logs_assert(|lines: &[&str]| {
+        let order: Vec<_> = ["first attempt", "second attempt", "unbuffered"]
+            .iter()
+            .map(|value| lines.iter().position(|line| line.contains(value)))
+            .collect();
+        match order.as_slice() {
+            [Some(a), Some(b), Some(c)] if a < b && b < c => Ok(()),
+            _ => Err(format!("unexpected order: {:?}", order)),
+        }
+    });
+}
@@ -2,7 +2,7 @@ use tracing_unwrap::OptionExt;
 
 #[test]
 #[tracing_test::traced_test]
-#[cfg_attr(not(feature = "log-location"), ignore)]
+#[cfg_attr(any(not(feature = "log-location"), feature = "otel-fields"), ignore)]
 fn log_location() {
     let _ = std::panic::catch_unwind(|| {
         Option::<()>::None.unwrap_or_log();
@@ -0,0 +1,46 @@
+use std::io::{self, Read, Write};
+use tracing_unwrap::{LoggedReader, LoggedWriter};
+
+/// A writer that accepts a few bytes, then fails.
+struct Full(usize);
+
+impl Write for Full {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.0 == 0 {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"));
+        }
+        let n = buf.len().min(self.0);
+        self.0 -= n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn reader() {
+    let mut reader = LoggedReader::new(&b"head"[..], "read frame");
+    let mut frame = [0; 8];
+
+    assert!(reader.read_exact(&mut frame[..2]).is_ok());
+    assert!(reader.read_exact(&mut frame).is_err());
+    assert_eq!(reader.bytes_read(), 4);
+    assert!(logs_contain(
+        "WARN reader: tracing_unwrap: read `read frame` failed after 4 bytes"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn panicking_writer() {
+    let mut writer = LoggedWriter::new(Full(3), "save snapshot").panicking();
+    let result = std::panic::catch_unwind(move || writer.write_all(b"snapshot"));
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "ERROR panicking_writer: tracing_unwrap: write `save snapshot` failed after 3 bytes: Custom"
+    ));
+}
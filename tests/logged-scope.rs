@@ -0,0 +1,32 @@
+use tracing_unwrap::logged_scope;
+
+#[test]
+#[tracing_test::traced_test]
+fn logged_scope() {
+    let rows = logged_scope!("count rows", { 3 });
+    assert_eq!(rows, 3);
+    assert!(!logs_contain("count rows"));
+
+    let result = std::panic::catch_unwind(|| {
+        logged_scope!("import users", {
+            let users: Vec<u32> = Vec::new();
+            users[0]
+        })
+    });
+    assert!(result.is_err());
+
+    logs_assert(|lines: &[&str]| {
+        let logged: Vec<_> = lines
+            .iter()
+            .filter(|line| line.contains("operation `import users`"))
+            .collect();
+        match logged.as_slice() {
+            [line]
+                if line.contains(" ERROR ") && line.contains("unwrap.method=\"logged_scope!\"") =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("unexpected events: {:?}", logged)),
+        }
+    });
+}
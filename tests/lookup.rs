@@ -0,0 +1,34 @@
+use std::collections::{BTreeMap, HashMap};
+use std::panic::catch_unwind;
+use tracing_unwrap::LookupExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn lookups() {
+    let mut ports = vec![80, 443];
+    assert_eq!(*ports.get_or_log(1), 443);
+    *ports.get_mut_or_log(0) = 8080;
+    assert_eq!(ports, [8080, 443]);
+
+    let mut users = HashMap::from([("alice".to_string(), 1)]);
+    assert_eq!(*users.get_or_log("alice"), 1);
+    *users.get_mut_or_log("alice") += 1;
+    assert_eq!(users["alice"], 2);
+
+    let limits = BTreeMap::from([(1, "low")]);
+    assert_eq!(*limits.get_or_log(&1), "low");
+
+    assert!(catch_unwind(|| *ports.get_or_log(5)).is_err());
+    assert!(catch_unwind(|| *users.get_or_log("bob")).is_err());
+    assert!(catch_unwind(|| *limits.get_or_log(&7)).is_err());
+
+    assert!(logs_contain(
+        "called `slice::get_or_log()` with an index out of bounds: index 5, len 2"
+    ));
+    assert!(logs_contain(
+        "called `HashMap::get_or_log()` with a missing key: \"bob\""
+    ));
+    assert!(logs_contain(
+        "called `BTreeMap::get_or_log()` with a missing key: 7"
+    ));
+}
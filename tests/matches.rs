@@ -0,0 +1,29 @@
+use tracing_unwrap::matches_or_log;
+
+#[derive(Debug)]
+enum State {
+    Idle,
+    Running { pid: u32 },
+}
+
+#[test]
+fn matching_values() {
+    matches_or_log!(State::Running { pid: 7 }, State::Running { .. });
+    matches_or_log!(Some(3), Some(n) if n > 2, "expected more than two");
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn mismatched_value() {
+    let result = std::panic::catch_unwind(|| {
+        matches_or_log!(State::Idle, State::Running { pid: 1..=100 });
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `matches_or_log!()` on a non-matching value: Idle"
+    ));
+    assert!(logs_contain(
+        "unwrap.details={pattern: \"State::Running { pid: 1..=100 }\"}"
+    ));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "nb")]
+
+use tracing_unwrap::{block_or_log, NbResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn unwrap_would_block() {
+    let result = std::panic::catch_unwind(|| {
+        nb::Result::<(), &str>::Err(nb::Error::WouldBlock).unwrap_or_log();
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("on a `WouldBlock` value"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn unwrap_driver_error() {
+    let result = std::panic::catch_unwind(|| {
+        nb::Result::<(), &str>::Err(nb::Error::Other("bus fault")).unwrap_or_log();
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("on an `Other` value: \"bus fault\""));
+}
+
+#[test]
+fn block_spins_until_ready() {
+    let mut polls = 0;
+    let value = block_or_log(|| {
+        polls += 1;
+        if polls < 3 {
+            Err(nb::Error::<()>::WouldBlock)
+        } else {
+            Ok(polls)
+        }
+    });
+
+    assert_eq!(value, 3);
+}
@@ -0,0 +1,31 @@
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use tracing_unwrap::{TcpListenerExt, TcpStreamExt, UdpSocketExt};
+
+#[test]
+fn connects() {
+    let listener = TcpListener::bind_or_log("127.0.0.1:0");
+    TcpStream::connect_or_log(listener.local_addr().unwrap());
+    UdpSocket::bind_or_log("127.0.0.1:0");
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn connection_refused() {
+    // Binding then dropping a listener leaves a port that refuses connections.
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let result = std::panic::catch_unwind(|| TcpStream::connect_or_log(addr));
+
+    assert!(result.is_err());
+    assert!(logs_contain(&format!(
+        "called `TcpStream::connect_or_log()` and connecting failed: address {}",
+        addr
+    )));
+    assert!(logs_contain(&format!(
+        "unwrap.details={{address: \"{}\"}}",
+        addr
+    )));
+    assert!(logs_contain("unwrap.error_kind=ConnectionRefused"));
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "nightly")]
+
+use std::io;
+use tracing_unwrap::{ErrorResultExt, ResultExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn declared_levels() {
+    Err::<(), _>(io::ErrorKind::NotFound).ok_or_log();
+    Err::<(), _>(io::Error::from(io::ErrorKind::PermissionDenied)).ok_or_log_error();
+    let result = std::panic::catch_unwind(|| {
+        Err::<(), _>(io::ErrorKind::NotFound).expect_or_log("open config")
+    });
+    // An error type that doesn't declare a level is logged as usual.
+    Err::<(), _>("plain").ok_or_log();
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "WARN declared_levels: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: NotFound"
+    ));
+    assert!(logs_contain(
+        "ERROR declared_levels: tracing_unwrap: called `Result::ok_or_log_error` on an `Err` value: Kind(PermissionDenied)"
+    ));
+    assert!(logs_contain(
+        "WARN declared_levels: tracing_unwrap: open config: NotFound"
+    ));
+    assert!(logs_contain(
+        "WARN declared_levels: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: \"plain\""
+    ));
+}
@@ -0,0 +1,34 @@
+use tracing::Level;
+use tracing_unwrap::{Escalation, ResultExt};
+
+fn discard(attempt: u32) {
+    Err::<(), _>(attempt).ok_or_log();
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn occurrence() {
+    // An escalation policy turns on per-callsite tracking.
+    tracing_unwrap::set_escalation(Some(Escalation::new(Level::ERROR)));
+    for attempt in 1..=3 {
+        discard(attempt);
+    }
+    Err::<(), _>("elsewhere").ok_or_log();
+    tracing_unwrap::set_escalation(None);
+
+    logs_assert(|lines: &[&str]| {
+        let occurrence = |value: &str| {
+            let line = lines.iter().find(|line| line.contains(value)).unwrap();
+            let rest = line.split("unwrap.occurrence=").nth(1)?;
+            rest.split_whitespace().next()
+        };
+        match (
+            occurrence("value: 1"),
+            occurrence("value: 3"),
+            occurrence("elsewhere"),
+        ) {
+            (Some("1"), Some("3"), Some("1")) => Ok(()),
+            occurrences => Err(format!("unexpected occurrences: {:?}", occurrences)),
+        }
+    });
+}
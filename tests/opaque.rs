@@ -0,0 +1,32 @@
+use std::panic::catch_unwind;
+use tracing_unwrap::ResultExt;
+
+/// An error that carries a secret, and can't be `Debug`-printed.
+struct Credentials {
+    #[allow(dead_code)]
+    password: &'static str,
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn opaque_errors() {
+    let failing = || {
+        Err::<(), _>(Credentials {
+            password: "hunter2",
+        })
+    };
+
+    assert_eq!(Ok::<_, Credentials>(1).unwrap_or_log_opaque(), 1);
+    assert_eq!(failing().ok_or_log_opaque(), None);
+    assert!(catch_unwind(|| failing().unwrap_or_log_opaque()).is_err());
+    assert!(catch_unwind(|| failing().expect_or_log_redacted("login failed")).is_err());
+
+    assert!(logs_contain(
+        "WARN opaque_errors: tracing_unwrap: called `Result::ok_or_log_opaque` on an `Err` value: opaque::Credentials"
+    ));
+    assert!(logs_contain(
+        "called `Result::unwrap_or_log_opaque()` on an `Err` value: opaque::Credentials"
+    ));
+    assert!(logs_contain("login failed"));
+    assert!(!logs_contain("hunter2"));
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "otel")]
+
+use opentelemetry::baggage::BaggageExt;
+use opentelemetry::{Context, KeyValue};
+use tracing_unwrap::ResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn baggage() {
+    Err::<(), _>("no context").ok_or_log();
+    assert!(!logs_contain("unwrap.baggage"));
+
+    let context = Context::current_with_baggage([
+        KeyValue::new("tenant", "acme"),
+        KeyValue::new("request_id", "42"),
+    ]);
+    let _guard = context.attach();
+    Err::<(), _>("in context").ok_or_log();
+
+    logs_assert(|lines: &[&str]| {
+        let line = lines
+            .iter()
+            .find(|line| line.contains("in context"))
+            .unwrap();
+        if line.contains("tenant=acme") && line.contains("request_id=42") {
+            Ok(())
+        } else {
+            Err(format!("missing baggage: {}", line))
+        }
+    });
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "otel-fields")]
+
+use tracing_unwrap::ResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn semantic_convention_fields() {
+    let _ = std::panic::catch_unwind(|| {
+        "x".parse::<u8>().unwrap_or_log();
+    });
+
+    assert!(logs_contain(
+        "exception.message=called `Result::unwrap_or_log()` on an `Err` value: ParseIntError { kind: InvalidDigit }"
+    ));
+    assert!(logs_contain(
+        "exception.type=\"core::num::error::ParseIntError\""
+    ));
+    assert!(!logs_contain("unwrap.filepath"));
+    if cfg!(feature = "log-location") {
+        assert!(logs_contain("code.filepath=\"tests/otel-fields.rs\""));
+        assert!(logs_contain("code.lineno=9"));
+    }
+}
@@ -0,0 +1,22 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing_unwrap::OptionExt;
+
+static PREVIOUS_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+#[tracing_test::traced_test]
+fn quiet_panic_hook() {
+    std::panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    tracing_unwrap::install_quiet_panic_hook();
+
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("logged"));
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 0);
+
+    let _ = std::panic::catch_unwind(|| panic!("not logged"));
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 1);
+
+    let _ = std::panic::take_hook();
+    assert!(logs_contain("logged"));
+}
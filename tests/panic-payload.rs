@@ -0,0 +1,18 @@
+#![cfg(feature = "panic-payload")]
+
+use tracing::Level;
+use tracing_unwrap::{ResultExt, UnwrapFailure};
+
+#[test]
+fn panic_payload() {
+    let payload =
+        std::panic::catch_unwind(|| Err::<(), _>("refused").expect_or_log("connect")).unwrap_err();
+
+    let failure = payload.downcast_ref::<UnwrapFailure>().unwrap();
+    assert_eq!(failure.method(), "Result::expect_or_log");
+    assert_eq!(failure.message(), "connect");
+    assert_eq!(failure.value(), Some("\"refused\""));
+    assert_eq!(failure.level(), Level::ERROR);
+    assert_eq!(failure.location().file(), "tests/panic-payload.rs");
+    assert_eq!(failure.to_string(), "connect: \"refused\"");
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "panic-hook")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing_unwrap::OptionExt;
+
+static PREVIOUS_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+#[tracing_test::traced_test]
+fn panics_to_tracing() {
+    std::panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    tracing_unwrap::init_panic_hook_chained();
+
+    let _ = std::panic::catch_unwind(|| panic!("plain panic {}", 1));
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("already logged"));
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 2);
+
+    tracing_unwrap::init_panic_hook();
+    let _ = std::panic::catch_unwind(|| std::hint::black_box(None::<()>).unwrap());
+    assert_eq!(PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst), 2);
+    let _ = std::panic::take_hook();
+
+    assert!(logs_contain(
+        "thread 'panics_to_tracing' panicked: plain panic 1 unwrap.filepath=\"tests/panic-to-tracing.rs\" unwrap.lineno=16"
+    ));
+    assert!(logs_contain(
+        "panicked: called `Option::unwrap()` on a `None` value"
+    ));
+    assert!(!logs_contain("panicked: already logged"));
+}
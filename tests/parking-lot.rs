@@ -0,0 +1,46 @@
+#![cfg(feature = "parking_lot")]
+
+use parking_lot::{Mutex, RwLock};
+use std::time::Duration;
+use tracing_unwrap::{ParkingLotMutexExt, ParkingLotRwLockExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn mutex() {
+    let mutex = Mutex::new(0);
+    assert!(mutex.try_lock_or_log("counter").is_some());
+
+    let _held = mutex.lock();
+    assert!(mutex.try_lock_or_log("counter").is_none());
+    assert!(mutex
+        .try_lock_for_or_log("counter", Duration::from_millis(10))
+        .is_none());
+
+    assert!(logs_contain("lock `counter` not acquired: held elsewhere"));
+    assert!(logs_contain(
+        "lock `counter` not acquired within 10ms: held elsewhere, waited"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn rwlock() {
+    let lock = RwLock::new(0);
+
+    let reading = lock.read();
+    assert!(lock.try_read_or_log("config").is_some());
+    assert!(lock.try_write_or_log("config").is_none());
+    drop(reading);
+
+    let _writing = lock.write();
+    assert!(lock.try_read_or_log("config").is_none());
+    assert!(lock
+        .try_write_for_or_log("config", Duration::from_millis(10))
+        .is_none());
+
+    assert!(logs_contain("lock `config` not acquired: held for reading"));
+    assert!(logs_contain("lock `config` not acquired: held for writing"));
+    assert!(logs_contain(
+        "lock `config` not acquired within 10ms: held for writing, waited"
+    ));
+}
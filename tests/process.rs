@@ -0,0 +1,17 @@
+#![cfg(unix)]
+
+use std::process::Command;
+use tracing_unwrap::ChildExt;
+
+#[test]
+fn wait_for_child() {
+    let mut child = Command::new("true").spawn().unwrap();
+    assert!(child.wait_or_log().success());
+}
+
+#[test]
+fn kill_child() {
+    let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+    child.kill_or_log();
+    assert!(!child.wait_or_log().success());
+}
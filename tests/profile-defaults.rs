@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fmt;
+use tracing::Level;
+use tracing_unwrap::{ErrorResultExt, Escalation, ProfileDefaults, ResultExt};
+
+#[derive(Debug)]
+struct Rejected(Password);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected {}", self.0)
+    }
+}
+
+impl Error for Rejected {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug)]
+struct Password(&'static str);
+
+impl fmt::Display for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "password {}", self.0)
+    }
+}
+
+impl Error for Password {}
+
+#[test]
+#[tracing_test::traced_test]
+fn profile_defaults() {
+    // Tests build with debug assertions, so only the debug defaults apply.
+    tracing_unwrap::set_release_defaults(Some(ProfileDefaults::new().with_level(Level::TRACE)));
+    tracing_unwrap::set_debug_defaults(Some(
+        ProfileDefaults::new().with_level(Level::INFO).redacted(),
+    ));
+    Err::<(), _>("hunter2").ok_or_log();
+    let fatal = std::panic::catch_unwind(|| Err::<(), _>("hunter3").unwrap_or_log());
+    let chosen =
+        std::panic::catch_unwind(|| Err::<(), _>("hunter4").unwrap_or_log_at(Level::ERROR));
+    assert!(fatal.is_err());
+    assert!(chosen.is_err());
+
+    // Neither an error nor its sources are logged.
+    Err::<(), _>(Rejected(Password("hunter6"))).ok_or_log_error();
+
+    // The panic is raised with the record as it was logged, escalated after
+    // the defaults were applied.
+    tracing_unwrap::set_escalation(Some(Escalation::new(Level::WARN)));
+    tracing_unwrap::set_panic_formatter(Some(|record| format!("panicked at {}", record.level())));
+    let escalated = std::panic::catch_unwind(|| Err::<(), _>("hunter5").unwrap_or_log());
+    tracing_unwrap::set_panic_formatter(None);
+    tracing_unwrap::set_escalation(None);
+    tracing_unwrap::set_debug_defaults(None);
+    tracing_unwrap::set_release_defaults(None);
+    let payload = escalated.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().unwrap(),
+        "panicked at WARN"
+    );
+
+    assert!(!logs_contain("hunter"));
+    assert!(logs_contain(
+        "WARN profile_defaults: tracing_unwrap: called `Result::ok_or_log` on an `Err` value: <redacted>"
+    ));
+    assert!(logs_contain(
+        "INFO profile_defaults: tracing_unwrap: called `Result::unwrap_or_log()` on an `Err` value: <redacted>"
+    ));
+    assert!(logs_contain(
+        "ERROR profile_defaults: tracing_unwrap: called `Result::unwrap_or_log_at()` on an `Err` value: <redacted>"
+    ));
+    assert!(logs_contain(
+        "WARN profile_defaults: tracing_unwrap: called `Result::ok_or_log_error` on an `Err` value: <redacted>"
+    ));
+    assert!(!logs_contain("unwrap.sources"));
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "nightly")]
+#![feature(error_generic_member_access)]
+
+use std::backtrace::Backtrace;
+use std::error::{Error, Request};
+use std::fmt;
+use tracing_unwrap::BoxErrorExt;
+
+#[derive(Debug)]
+struct Captured(Backtrace);
+
+impl fmt::Display for Captured {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("captured")
+    }
+}
+
+impl Error for Captured {
+    fn provide<'a>(&'a self, request: &mut Request<'a>) {
+        request.provide_ref::<Backtrace>(&self.0);
+    }
+}
+
+fn create() -> Captured {
+    Captured(Backtrace::force_capture())
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn provided_backtrace() {
+    let error: Box<dyn Error> = Box::new(create());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        error.downcast_or_log::<fmt::Error>()
+    }));
+    assert!(result.is_err());
+
+    // The backtrace is the error's, which was captured in `create`.
+    assert!(logs_contain("unwrap.stacktrace="));
+    assert!(logs_contain("provided_backtrace::create"));
+}
+
+/// An error with an endless chain of sources, none of which has a backtrace.
+#[derive(Debug)]
+struct Cyclic;
+
+impl fmt::Display for Cyclic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cyclic")
+    }
+}
+
+impl Error for Cyclic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&Cyclic)
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cyclic_source_chain() {
+    tracing_unwrap::set_max_source_depth(3);
+    let error: Box<dyn Error> = Box::new(Cyclic);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        error.downcast_or_log::<fmt::Error>()
+    }));
+    assert!(result.is_err());
+    assert!(logs_contain("unwrap.sources_truncated=3"));
+}
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing_unwrap::{
+    Clock, FailureRecord, FailureReporter, RateLimit, ResultExt, TracingReporter,
+};
+
+/// A clock that only moves when told to.
+struct ManualClock(Arc<AtomicU64>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_secs(self.0.load(Ordering::SeqCst))
+    }
+}
+
+static REPORTED: Mutex<Vec<(String, Option<u64>)>> = Mutex::new(Vec::new());
+
+/// Records every reported failure, including the summaries that the rate
+/// limit reports from its own thread.
+struct Recorder;
+
+impl FailureReporter for Recorder {
+    fn report(&self, record: &FailureRecord<'_>) {
+        let reported = (record.to_string(), record.suppressed());
+        REPORTED.lock().unwrap().push(reported);
+    }
+}
+
+fn poll(attempt: u32) {
+    Err::<(), _>(format!("peer down, attempt {}", attempt)).ok_or_log();
+}
+
+fn flaky(attempt: u32) {
+    Err::<(), _>(format!("flaky, attempt {}", attempt)).ok_or_log();
+}
+
+fn quiet(attempt: u32) {
+    Err::<(), _>(format!("quiet, attempt {}", attempt)).ok_or_log();
+}
+
+fn summaries() -> usize {
+    reported()
+        .iter()
+        .filter(|(_, suppressed)| suppressed.is_some())
+        .count()
+}
+
+fn reported() -> Vec<(String, Option<u64>)> {
+    REPORTED.lock().unwrap().clone()
+}
+
+#[test]
+fn rate_limit() {
+    let seconds = Arc::new(AtomicU64::new(0));
+    tracing_unwrap::set_clock(Some(Box::new(ManualClock(seconds.clone()))));
+    tracing_unwrap::set_reporter(Some(Box::new((TracingReporter, Recorder))));
+    tracing_unwrap::set_rate_limit(Some(RateLimit::new(Duration::from_secs(10)).with_burst(2)));
+
+    // The suppressed count is summarized once the window has closed, before
+    // the next failure is logged.
+    for attempt in 0..5 {
+        poll(attempt);
+    }
+    seconds.store(10, Ordering::SeqCst);
+    poll(5);
+
+    let polled: Vec<_> = reported()
+        .into_iter()
+        .filter(|(msg, _)| msg.contains("peer down") || msg.contains("suppressed"))
+        .collect();
+    match polled.as_slice() {
+        [(first, None), (second, None), (summary, Some(3)), (last, None)]
+            if first.contains("attempt 0")
+                && second.contains("attempt 1")
+                && summary.starts_with(
+                    "suppressed 3 more failures of `Result::ok_or_log` at tests/rate-limit.rs:",
+                )
+                && last.contains("attempt 5") => {}
+        _ => panic!("unexpected reports: {:?}", polled),
+    }
+
+    // A callsite that stops failing is still summarized once its window
+    // closes.
+    for attempt in 0..3 {
+        flaky(attempt);
+    }
+    seconds.store(20, Ordering::SeqCst);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !reported()
+        .iter()
+        .any(|(_, suppressed)| *suppressed == Some(1))
+    {
+        assert!(Instant::now() < deadline, "no summary: {:?}", reported());
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // Summaries are filtered like any other event.
+    for attempt in 0..3 {
+        quiet(attempt);
+    }
+    let summarized = summaries();
+    tracing_unwrap::set_enabled(false);
+    tracing_unwrap::set_rate_limit(None);
+    tracing_unwrap::set_enabled(true);
+    assert_eq!(summaries(), summarized);
+
+    tracing_unwrap::set_reporter(None);
+    tracing_unwrap::set_clock(None);
+    assert_eq!(
+        reported()
+            .iter()
+            .filter(|(msg, _)| msg.contains("flaky"))
+            .count(),
+        2
+    );
+}
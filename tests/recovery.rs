@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing_unwrap::{Clock, ResultExt};
+
+/// A clock that only moves when told to.
+struct ManualClock(Arc<AtomicU64>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_secs(self.0.load(Ordering::SeqCst))
+    }
+}
+
+fn poll(result: Result<(), &str>) -> Option<()> {
+    result.ok_or_log()
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn recovery_events() {
+    let seconds = Arc::new(AtomicU64::new(100));
+    tracing_unwrap::set_clock(Some(Box::new(ManualClock(seconds.clone()))));
+    tracing_unwrap::set_recovery_events(true);
+
+    // A success with no outage is not reported.
+    poll(Ok(()));
+    assert!(!logs_contain("recovered"));
+
+    poll(Err("down"));
+    seconds.store(130, Ordering::SeqCst);
+    poll(Err("still down"));
+    seconds.store(145, Ordering::SeqCst);
+    poll(Ok(()));
+    // Only the first success ends the outage.
+    poll(Ok(()));
+
+    tracing_unwrap::set_recovery_events(false);
+    poll(Err("down again"));
+    poll(Ok(()));
+    tracing_unwrap::set_clock(None);
+
+    logs_assert(|lines: &[&str]| {
+        let recovered: Vec<_> = lines
+            .iter()
+            .filter(|line| line.contains("recovered after"))
+            .collect();
+        match recovered.as_slice() {
+            [line]
+                if line.contains(" INFO ")
+                    && line.contains("recovered after 2 failures over 45s")
+                    && line.contains("unwrap.method=\"Result::ok_or_log\"") =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("unexpected recovery events: {:?}", recovered)),
+        }
+    });
+}
@@ -0,0 +1,34 @@
+use std::sync::Mutex;
+use tracing_unwrap::{FailureRecord, FailureReporter, OptionExt, ResultExt, TracingReporter};
+
+static REPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Queue;
+
+impl FailureReporter for Queue {
+    fn report(&self, record: &FailureRecord<'_>) {
+        let entry = format!("{} (fatal: {})", record, record.is_fatal());
+        REPORTED.lock().unwrap().push(entry);
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn reporters() {
+    tracing_unwrap::set_reporter(Some(Box::new(Queue)));
+    Result::<(), _>::Err("queued only").ok_or_log();
+
+    tracing_unwrap::set_reporter(Some(Box::new((TracingReporter, Queue))));
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.expect_or_log("queued and logged"));
+    tracing_unwrap::set_reporter(None);
+
+    assert_eq!(
+        *REPORTED.lock().unwrap(),
+        [
+            "called `Result::ok_or_log` on an `Err` value: \"queued only\" (fatal: false)",
+            "queued and logged (fatal: true)",
+        ]
+    );
+    assert!(!logs_contain("queued only"));
+    assert!(logs_contain("queued and logged"));
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "tokio")]
+
+use std::time::Duration;
+use tracing_unwrap::{retry_or_log, Backoff};
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn retries_until_success() {
+    let mut failures = 2;
+    let backoff = Backoff::exponential(Duration::from_millis(1));
+    let result = retry_or_log("connect", backoff, || {
+        let outcome = if failures > 0 { Err("refused") } else { Ok(7) };
+        failures -= 1;
+        async move { outcome }
+    })
+    .await;
+
+    assert_eq!(result, Ok(7));
+    assert!(logs_contain(
+        "WARN retries_until_success: tracing_unwrap: attempt 1 of operation `connect` failed, retrying in 1ms: \"refused\""
+    ));
+    assert!(logs_contain(
+        "unwrap.details={attempt: 2, retry_delay: 2ms}"
+    ));
+    assert!(!logs_contain("ERROR"));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn gives_up() {
+    let backoff = Backoff::fixed(Duration::from_millis(1)).with_max_attempts(2);
+    let result: Result<(), _> = retry_or_log("connect", backoff, || async { Err("refused") }).await;
+
+    assert_eq!(result, Err("refused"));
+    assert!(logs_contain(
+        "ERROR gives_up: tracing_unwrap: operation `connect` failed after 2 attempts: \"refused\""
+    ));
+}
@@ -0,0 +1,25 @@
+use tracing_unwrap::ResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn sampling_priority() {
+    tracing_unwrap::set_sampling_priority(Some(2));
+    let span = tracing::info_span!("request", sampling.priority = tracing::field::Empty);
+    let fatal = span.in_scope(|| {
+        Err::<(), _>("discarded").ok_or_log();
+        std::panic::catch_unwind(|| Err::<(), _>("fatal").unwrap_or_log())
+    });
+    tracing_unwrap::set_sampling_priority(None);
+    assert!(fatal.is_err());
+
+    logs_assert(|lines: &[&str]| {
+        let prioritized = |value: &str| {
+            let line = lines.iter().find(|line| line.contains(value)).unwrap();
+            line.contains("sampling.priority=2")
+        };
+        match (prioritized("discarded"), prioritized("fatal")) {
+            (false, true) => Ok(()),
+            priorities => Err(format!("unexpected priorities: {:?}", priorities)),
+        }
+    });
+}
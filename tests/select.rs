@@ -0,0 +1,58 @@
+use futures::executor::block_on;
+use std::future::{pending, ready};
+use tracing_unwrap::{select_or_log, try_select_or_log};
+
+#[test]
+fn first_ready_branch_wins() {
+    let value = block_on(async {
+        select_or_log! {
+            never = pending::<Result<u8, ()>>(),
+            ready = ready(Ok::<_, String>(7)),
+        }
+    });
+    assert_eq!(value, 7);
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_branch_panics() {
+    let result = std::panic::catch_unwind(|| {
+        block_on(async {
+            select_or_log! {
+                primary = ready(Err::<u8, _>("connection reset")),
+                replica = pending::<Result<u8, ()>>(),
+            }
+        })
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("ERROR failed_branch_panics: tracing_unwrap: select branch `primary` failed: \"connection reset\""));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_branch_propagates() {
+    let result: Result<u8, &str> = block_on(async {
+        try_select_or_log! {
+            replica = pending(),
+            primary = ready(Err("timeout")),
+        }
+    });
+
+    assert_eq!(result, Err("timeout"));
+    assert!(logs_contain("WARN failed_branch_propagates: tracing_unwrap: select branch `primary` failed: \"timeout\""));
+}
+
+#[test]
+fn labels_do_not_shadow_futures() {
+    // The second branch's future is the variable `fast`, not the pinned
+    // future of the first branch, which is labelled `fast`.
+    let fast = ready(Ok::<u8, ()>(3));
+    let value = block_on(async {
+        select_or_log! {
+            fast = pending::<Result<u8, ()>>(),
+            slow = fast,
+        }
+    });
+    assert_eq!(value, 3);
+}
@@ -0,0 +1,26 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use tracing_unwrap::{ArcExt, RcExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn arc_still_shared() {
+    let result = std::panic::catch_unwind(|| {
+        let arc = Arc::new(5);
+        let _other = Arc::clone(&arc);
+        let _weak = Arc::downgrade(&arc);
+        arc.try_unwrap_or_log();
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "on a shared `Arc`: strong count 2, weak count 1"
+    ));
+}
+
+#[test]
+fn rc_exclusive() {
+    let mut rc = Rc::new(5);
+    *rc.get_mut_or_log() += 1;
+    assert_eq!(rc.into_inner_or_log(), 6);
+}
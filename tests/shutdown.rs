@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tracing_unwrap::OptionExt;
+
+static RAN: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+#[tracing_test::traced_test]
+fn shutdown_hooks() {
+    // Runs once, before the panic.
+    tracing_unwrap::set_shutdown_hook(
+        || {
+            RAN.fetch_add(1, Ordering::SeqCst);
+        },
+        Duration::from_secs(5),
+    );
+    for _ in 0..2 {
+        let _ = std::panic::catch_unwind(|| Option::<()>::None.unwrap_or_log());
+    }
+    assert_eq!(RAN.load(Ordering::SeqCst), 1);
+
+    // Async hooks are driven to completion.
+    tracing_unwrap::set_async_shutdown_hook(
+        || async {
+            RAN.fetch_add(1, Ordering::SeqCst);
+        },
+        Duration::from_secs(5),
+    );
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.unwrap_or_log());
+    assert_eq!(RAN.load(Ordering::SeqCst), 2);
+
+    // Slow hooks are abandoned after the timeout.
+    tracing_unwrap::set_shutdown_hook(
+        || std::thread::sleep(Duration::from_secs(60)),
+        Duration::from_millis(50),
+    );
+    let started = Instant::now();
+    let _ = std::panic::catch_unwind(|| Option::<()>::None.unwrap_or_log());
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(logs_contain("shutdown hook did not finish within 50ms"));
+}
@@ -0,0 +1,60 @@
+use futures::executor::block_on;
+use futures::future::ready;
+use tracing_unwrap::ResultFutureExt;
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn span_capture() {
+    use std::time::Duration;
+    use tracing_unwrap::{try_timeout_or_log, SpanCapture};
+
+    let slow = || tokio::time::sleep(Duration::from_secs(60));
+
+    let polled = tracing::info_span!("polled")
+        .in_scope(|| try_timeout_or_log(Duration::from_millis(10), "poll-time", slow()));
+    tracing_unwrap::set_async_span_capture(SpanCapture::Construction);
+    let constructed = tracing::info_span!("constructed")
+        .in_scope(|| try_timeout_or_log(Duration::from_millis(10), "construction-time", slow()));
+    tracing_unwrap::set_async_span_capture(SpanCapture::Poll);
+
+    assert!(polled.await.is_err());
+    assert!(constructed.await.is_err());
+
+    logs_assert(|lines: &[&str]| {
+        let in_span = |label: &str| {
+            let line = lines.iter().find(|line| line.contains(label)).unwrap();
+            line.contains("polled") || line.contains("constructed:")
+        };
+        match (in_span("`poll-time`"), in_span("`construction-time`")) {
+            (false, true) => Ok(()),
+            spans => Err(format!("unexpected spans: {:?}", spans)),
+        }
+    });
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn in_current_span() {
+    // Each future is created in a request span, then polled outside of it.
+    let request = tracing::info_span!("request");
+    let (polled, current) = request.in_scope(|| {
+        (
+            ready(Err::<(), _>("polled")).ok_or_log(),
+            ready(Err::<(), _>("current")).ok_or_log_in_current_span(),
+        )
+    });
+    block_on(polled);
+    block_on(current);
+
+    logs_assert(|lines: &[&str]| {
+        let in_request = |value: &str| {
+            let line = lines.iter().find(|line| line.contains(value)).unwrap();
+            line.contains("request:")
+        };
+        match (in_request("\"polled\""), in_request("\"current\"")) {
+            (false, true) => Ok(()),
+            spans => Err(format!("unexpected spans: {:?}", spans)),
+        }
+    });
+}
@@ -0,0 +1,65 @@
+use std::panic::AssertUnwindSafe;
+use std::thread;
+use tracing_unwrap::{scope_or_log, BuilderExt, JoinHandleExt};
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_spawn() {
+    // No system can reserve a stack this large.
+    let builder = thread::Builder::new().stack_size(1 << 60);
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        builder.spawn_or_log("huge", || ()).join_or_log()
+    }));
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `Builder::spawn_or_log()` and spawning failed: thread \"huge\", error kind"
+    ));
+}
+
+#[test]
+fn named_thread() {
+    let handle = thread::Builder::new()
+        .spawn_or_log("worker", || thread::current().name().map(str::to_owned));
+    assert_eq!(handle.join_or_log().as_deref(), Some("worker"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_scoped_thread() {
+    let result = std::panic::catch_unwind(|| {
+        scope_or_log(|scope| {
+            thread::Builder::new().spawn_scoped_or_log(scope, "parser", || panic!("bad input"));
+            thread::Builder::new().spawn_scoped_or_log(scope, "writer", || ());
+        })
+    });
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `scope_or_log()` and a scoped thread panicked: thread \"parser\", panic message \"bad input\""
+    ));
+    assert!(!logs_contain("writer"));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn joined_scoped_thread() {
+    let sum = scope_or_log(|scope| {
+        let a = thread::Builder::new().spawn_scoped_or_log(scope, "a", || 2);
+        let b = thread::Builder::new().spawn_scoped_or_log(scope, "b", || 3);
+        a.join_or_log() + b.join_or_log()
+    });
+    assert_eq!(sum, 5);
+
+    let result = std::panic::catch_unwind(|| {
+        scope_or_log(|scope| {
+            thread::Builder::new()
+                .spawn_scoped_or_log(scope, "reader", || panic!("eof"))
+                .join_or_log()
+        })
+    });
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `ScopedJoinHandle::join_or_log()` on a thread that panicked: thread \"reader\", panic message \"eof\""
+    ));
+    assert!(!logs_contain("called `scope_or_log()`"));
+}
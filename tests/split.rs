@@ -0,0 +1,31 @@
+use tracing_unwrap::StrExt;
+
+#[test]
+fn split_and_strip() {
+    assert_eq!("key=value".split_once_or_log('='), ("key", "value"));
+    assert_eq!("a::b::c".rsplit_once_or_log("::"), ("a::b", "c"));
+    assert_eq!("v1.2".strip_prefix_or_log('v'), "1.2");
+    assert_eq!("line\r\n".strip_suffix_or_log("\r\n"), "line");
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn split_without_delimiter() {
+    let result = std::panic::catch_unwind(|| {
+        "key value".split_once_or_log('=');
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("delimiter '=', input \"key value\""));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn long_input_is_truncated() {
+    let result = std::panic::catch_unwind(|| {
+        "x".repeat(100).split_once_or_log("=");
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(&format!("input \"{}\"...", "x".repeat(64))));
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "structured-errors")]
+
+use std::fmt;
+use tracing_unwrap::{ErrorResultExt, ResultExt};
+
+#[derive(Debug)]
+struct ConfigError(std::io::Error);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("config unreadable")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn error_field() {
+    Err::<(), _>(ConfigError(std::io::Error::other("disk offline"))).ok_or_log_error();
+    assert!(logs_contain(
+        "error=config unreadable error.sources=[disk offline]"
+    ));
+
+    Err::<(), _>("not an error type").ok_or_log();
+    assert!(logs_contain("error=\"not an error type\""));
+}
+
+/// An error with an endless chain of sources.
+#[derive(Debug)]
+struct Cyclic;
+
+impl fmt::Display for Cyclic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cyclic")
+    }
+}
+
+impl std::error::Error for Cyclic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&Cyclic)
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn cyclic_error_field() {
+    Err::<(), _>(Cyclic).ok_or_log_error();
+    assert!(logs_contain("error=Cyclic"));
+}
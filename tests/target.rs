@@ -0,0 +1,29 @@
+use tracing_unwrap::ResultExt;
+
+/// The target set at build time through `TRACING_UNWRAP_TARGET`, if any.
+const TARGET: &str = match option_env!("TRACING_UNWRAP_TARGET") {
+    Some(target) => target,
+    None => "tracing_unwrap",
+};
+
+fn poll(result: Result<(), &str>) -> Option<()> {
+    result.ok_or_log()
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn event_target() {
+    tracing_unwrap::set_recovery_events(true);
+    poll(Err("unreachable"));
+    poll(Ok(()));
+    tracing_unwrap::set_recovery_events(false);
+
+    assert!(logs_contain(&format!(
+        "WARN event_target: {}: called `Result::ok_or_log` on an `Err` value",
+        TARGET
+    )));
+    assert!(logs_contain(&format!(
+        "INFO event_target: {}: recovered after 1 failures",
+        TARGET
+    )));
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "termination-log")]
+
+use tracing_unwrap::ResultExt;
+
+#[test]
+fn termination_log() {
+    let path = std::env::temp_dir().join(format!("termination-log-{}", std::process::id()));
+    tracing_unwrap::set_termination_log_path(&path);
+
+    let result = std::panic::catch_unwind(|| {
+        Err::<(), _>("disk full").expect_or_log("failed to persist state");
+    });
+    assert!(result.is_err());
+
+    let summary = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut lines = summary.lines();
+    assert_eq!(lines.next(), Some("failed to persist state: \"disk full\""));
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("at tests/termination-log.rs:11:"));
+    assert!(lines.next().unwrap().ends_with("(Result::expect_or_log)"));
+}
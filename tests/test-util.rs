@@ -0,0 +1,87 @@
+#![cfg(feature = "test-util")]
+
+use std::error::Error;
+use std::fmt;
+use tracing::Level;
+use tracing_unwrap::test_util::Replay;
+use tracing_unwrap::{ErrorResultExt, ResultExt};
+
+#[test]
+fn buffers_events() {
+    let replay = Replay::start();
+    let line = line!() + 1;
+    let _ = std::panic::catch_unwind(|| Result::<(), _>::Err("boom").unwrap_or_log());
+
+    let events = replay.events();
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.level(), Level::ERROR);
+    assert_eq!(event.location().file(), "tests/test-util.rs");
+    assert_eq!(event.location().line(), line);
+    assert_eq!(event.method(), "Result::unwrap_or_log");
+    assert_eq!(event.value(), Some("\"boom\""));
+    assert!(!event.is_during_unwind());
+
+    // Replayed with the fields after the message.
+    let replayed = event.to_string();
+    let callsite_id = event.field("unwrap.callsite_id").unwrap();
+    assert_eq!(callsite_id.len(), 16);
+    assert!(replayed.starts_with("ERROR tests/test-util.rs:"));
+    assert!(replayed.contains(&format!(
+        "called `Result::unwrap_or_log()` on an `Err` value: \"boom\" \
+         unwrap.callsite_id={} unwrap.method=\"Result::unwrap_or_log\" unwrap.value=\"boom\"",
+        callsite_id
+    )));
+}
+
+#[derive(Debug)]
+struct Rejected(Timeout);
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("rejected")
+    }
+}
+
+impl Error for Rejected {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+#[test]
+fn buffers_fields() {
+    let replay = Replay::start();
+    Err::<(), _>(Rejected(Timeout)).ok_or_log_error();
+
+    let events = replay.events();
+    let event = &events[0];
+    assert_eq!(event.field("unwrap.sources"), Some("\"timed out\""));
+    assert_eq!(event.field("unwrap.during_unwind"), None);
+    assert!(event
+        .fields()
+        .any(|(name, value)| name == "unwrap.method" && value == "\"Result::ok_or_log_error\""));
+    assert!(event.to_string().contains(" unwrap.sources=\"timed out\""));
+}
+
+#[test]
+fn nothing_buffered_without_guard() {
+    {
+        let _replay = Replay::start();
+    }
+    let replay = Replay::start();
+    drop(Replay::start());
+    Result::<(), _>::Err("discarded").ok_or_log();
+    assert_eq!(replay.events().len(), 1);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "tokio")]
+
+use std::time::Duration;
+use tracing_unwrap::{timeout_or_log, try_timeout_or_log};
+
+#[tokio::test]
+async fn completes_in_time() {
+    let value = timeout_or_log(Duration::from_secs(5), "fast", async { 5 }).await;
+    assert_eq!(value, 5);
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn times_out() {
+    let slow = tokio::time::sleep(Duration::from_secs(60));
+    let result = try_timeout_or_log(Duration::from_millis(10), "fetch config", slow).await;
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "operation `fetch config` timed out after 10ms: elapsed"
+    ));
+}
@@ -0,0 +1,18 @@
+use tracing_unwrap::OptionResultExt;
+
+#[test]
+fn transposes() {
+    assert_eq!(None::<Result<u8, ()>>.transpose_or_log(), None);
+    assert_eq!(Some(Ok::<_, ()>(5)).transpose_or_log(), Some(5));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn present_but_failed() {
+    let result = std::panic::catch_unwind(|| Some(Err::<u8, _>("corrupt")).transpose_or_log());
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `Option::transpose_or_log()` on a `Some(Err)` value: \"corrupt\""
+    ));
+}
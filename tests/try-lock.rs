@@ -0,0 +1,41 @@
+use std::sync::{Mutex, RwLock};
+use tracing_unwrap::TryLockResultExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn contended() {
+    let lock = RwLock::new(5);
+    let _writer = lock.write().unwrap();
+
+    assert!(lock.try_read().try_lock_or_log().is_none());
+    assert!(std::panic::catch_unwind(|| lock.try_read().lock_or_log()).is_err());
+
+    assert!(logs_contain(
+        "WARN contended: tracing_unwrap: called `TryLockResult::try_lock_or_log()` on a lock held elsewhere"
+    ));
+    assert!(logs_contain(
+        "WARN contended: tracing_unwrap: called `TryLockResult::lock_or_log()` on a lock held elsewhere"
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn poisoned() {
+    let mutex = Mutex::new(5);
+    let _ = std::panic::catch_unwind(|| {
+        let _guard = mutex.lock().unwrap();
+        panic!("poison");
+    });
+
+    assert!(std::panic::catch_unwind(|| mutex.try_lock().try_lock_or_log()).is_err());
+    assert!(logs_contain(
+        "ERROR poisoned: tracing_unwrap: called `TryLockResult::try_lock_or_log()` on a poisoned lock"
+    ));
+}
+
+#[test]
+fn uncontended() {
+    let mutex = Mutex::new(5);
+    *mutex.try_lock().lock_or_log() += 1;
+    assert_eq!(*mutex.try_lock().try_lock_or_log().unwrap(), 6);
+}
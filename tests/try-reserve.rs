@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+use tracing_unwrap::TryReserveExt;
+
+#[test]
+#[tracing_test::traced_test]
+fn failed_reserve() {
+    let result = std::panic::catch_unwind(|| {
+        let mut v: Vec<u64> = Vec::new();
+        v.reserve_or_log(usize::MAX);
+    });
+    assert!(result.is_err());
+    assert!(logs_contain(&format!(
+        "called `Vec::reserve_or_log()` and the allocation failed: additional {}, capacity 0, TryReserveError {{ kind: CapacityOverflow }}",
+        usize::MAX
+    )));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn discarded_reserve() {
+    let mut s = String::from("abc");
+    assert!(!s.try_reserve_or_log(usize::MAX));
+    assert!(logs_contain(&format!(
+        "called `String::try_reserve_or_log()` and the allocation failed: additional {}, capacity 3",
+        usize::MAX
+    )));
+    assert!(logs_contain(" WARN "));
+
+    let mut map: HashMap<u32, u32> = HashMap::new();
+    assert!(map.try_reserve_or_log(16));
+    assert!(map.capacity() >= 16);
+}
@@ -0,0 +1,24 @@
+use tracing_unwrap::ResultExt;
+
+struct DiscardsOnDrop;
+
+impl Drop for DiscardsOnDrop {
+    fn drop(&mut self) {
+        Result::<(), _>::Err("cleanup failed").ok_or_log();
+    }
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn discard_during_unwind() {
+    let result = std::panic::catch_unwind(|| {
+        let _guard = DiscardsOnDrop;
+        panic!("original failure");
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain("unwrap.during_unwind=true"));
+    assert!(logs_contain(
+        "failure during unwind: called `Result::ok_or_log` on an `Err` value: \"cleanup failed\""
+    ));
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use tracing_unwrap::unwrap_all_or_log;
+
+#[test]
+fn all_present() {
+    let map = HashMap::from([("port", "8080")]);
+    let (port, parsed) = unwrap_all_or_log!(map.get("port"), "42".parse::<u8>());
+    assert_eq!((*port, parsed), ("8080", 42));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn every_failure_logged_once() {
+    let map = HashMap::from([("port", "8080")]);
+    let result = std::panic::catch_unwind(|| {
+        unwrap_all_or_log!(map.get("host"), "42".parse::<u8>(), "x".parse::<u8>());
+    });
+
+    assert!(result.is_err());
+    assert!(logs_contain(
+        "called `unwrap_all_or_log!()` with failed expressions: \
+         [`map.get(\"host\")`: None, `\"x\".parse::<u8>()`: Err(ParseIntError { kind: InvalidDigit })]"
+    ));
+}
+
+#[test]
+fn more_than_twelve() {
+    let (first, .., thirteenth, last) = unwrap_all_or_log!(
+        Some(1),
+        Some(2),
+        Some(3),
+        Some(4),
+        Some(5),
+        Some(6),
+        Some(7),
+        Some(8),
+        Some(9),
+        Some(10),
+        Some(11),
+        Some(12),
+        Ok::<_, ()>(13),
+        Some(14),
+    );
+    assert_eq!((first, thirteenth, last), (1, 13, 14));
+}
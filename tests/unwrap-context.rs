@@ -0,0 +1,51 @@
+#![cfg(feature = "attributes")]
+
+use tracing_unwrap::{unwrap_context, OptionExt, ResultExt};
+
+#[unwrap_context("loading user profile", user_id)]
+fn load_profile(user_id: u64, name: Option<&str>) -> String {
+    name.expect_or_log("profile has no name").to_owned()
+}
+
+#[unwrap_context("handling request")]
+fn handle(user_id: u64) -> Result<String, &'static str> {
+    Err::<(), _>("cache miss").ok_or_log();
+    Ok(load_profile(user_id, None))
+}
+
+#[unwrap_context("polling")]
+async fn poll(result: Result<(), &'static str>) -> Option<()> {
+    std::future::ready(()).await;
+    result.ok_or_log()
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn unwrap_context() {
+    let result = std::panic::catch_unwind(|| handle(42));
+    assert!(result.is_err());
+    futures::executor::block_on(poll(Err("not ready")));
+    Err::<(), _>("outside").ok_or_log();
+
+    logs_assert(|lines: &[&str]| {
+        let context = |value: &str| {
+            let line = lines.iter().find(|line| line.contains(value)).unwrap();
+            let (_, context) = line.split_once("unwrap.context=")?;
+            context.split('"').nth(1)
+        };
+        match (
+            context("cache miss"),
+            context("profile has no name"),
+            context("not ready"),
+            context("outside"),
+        ) {
+            (
+                Some("handling request"),
+                Some("handling request: loading user profile (user_id=42)"),
+                Some("polling"),
+                None,
+            ) => Ok(()),
+            contexts => Err(format!("unexpected contexts: {:?}", contexts)),
+        }
+    });
+}
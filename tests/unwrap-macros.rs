@@ -0,0 +1,23 @@
+use std::panic::catch_unwind;
+use tracing_unwrap::{expect_or_log, unwrap_or_log};
+
+fn lookup(id: u32) -> Option<&'static str> {
+    (id == 1).then_some("alice")
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn unwrap_macros() {
+    assert_eq!(unwrap_or_log!("7".parse::<u8>()), 7);
+    assert_eq!(expect_or_log!(lookup(1), "no such user"), "alice");
+
+    assert!(catch_unwind(|| unwrap_or_log!("x".parse::<u8>())).is_err());
+    assert!(catch_unwind(|| expect_or_log!(lookup(2), "no such user")).is_err());
+
+    assert!(logs_contain(
+        "called `unwrap_or_log!()` on an `Err` value: ParseIntError { kind: InvalidDigit } unwrap.details={expression: \"\\\"x\\\".parse::<u8>()\"}"
+    ));
+    assert!(logs_contain(
+        "no such user unwrap.details={expression: \"lookup(2)\"}"
+    ));
+}